@@ -1,11 +1,45 @@
-use crate::sql::types::{Column, TableSchema};
+use crate::sql::types::{Column, ForeignKey, TableSchema, Value};
 use crate::storage::{Result, StorageEngine, StorageError};
 use serde::{Deserialize, Serialize};
 
+/// A column as persisted in a `CatalogEntry`: the `(name, type)` pair plus the
+/// constraints `Catalog::create_table`/insert-time enforcement needs back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ColumnEntry {
+    name: String,
+    data_type: String,
+    #[serde(default)]
+    not_null: bool,
+    #[serde(default)]
+    primary_key: bool,
+    #[serde(default)]
+    unique: bool,
+    #[serde(default)]
+    default: Option<Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CatalogEntry {
     name: String,
-    columns: Vec<(String, String)>,
+    columns: Vec<ColumnEntry>,
+    #[serde(default)]
+    indexes: Vec<String>,
+    /// Columns backed by a `CREATE INDEX`-declared Roaring-bitmap posting list
+    /// (see `executor::bitmap`), tracked separately from `indexes` since the two
+    /// are different physical index implementations.
+    #[serde(default)]
+    bitmap_indexes: Vec<String>,
+    #[serde(default)]
+    unique_groups: Vec<Vec<String>>,
+    #[serde(default)]
+    foreign_keys: Vec<ForeignKey>,
+}
+
+/// Metadata describing a single-column index, as returned by [`Catalog::index_for_column`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+    pub table: String,
+    pub column: String,
 }
 
 pub struct Catalog {
@@ -14,12 +48,28 @@ pub struct Catalog {
 
 impl Catalog {
     const CATALOG_PREFIX: &'static [u8] = b"catalog:";
+    /// Separate key prefix for `CREATE TEMPORARY TABLE ... AS` relations, so they
+    /// can be enumerated and dropped as a batch at session end instead of
+    /// persisting alongside base tables under `CATALOG_PREFIX`.
+    const TEMP_CATALOG_PREFIX: &'static [u8] = b"catalog_tmp:";
 
     pub fn new(storage: StorageEngine) -> Self {
         Self { storage }
     }
 
-    pub fn create_table(&self, name: &str, columns: Vec<Column>) -> Result<()> {
+    /// Creates `name` with `columns`, plus any composite `unique_groups` and
+    /// `foreign_keys` declared as table-level constraints (single-column
+    /// `PRIMARY KEY`/`UNIQUE` constraints are expected to already be folded into
+    /// the matching `Column`'s flags by the caller). Single-column unique/primary
+    /// key columns are also backed by an index, so insert-time uniqueness checks
+    /// and equality lookups can use `scan_index` instead of a full table scan.
+    pub fn create_table(
+        &self,
+        name: &str,
+        columns: Vec<Column>,
+        foreign_keys: Vec<ForeignKey>,
+        unique_groups: Vec<Vec<String>>,
+    ) -> Result<()> {
         let key = Self::table_key(name);
 
         if self.storage.get(&key)?.is_some() {
@@ -29,45 +79,163 @@ impl Catalog {
             )));
         }
 
-        let cols: Vec<(String, String)> = columns
+        let mut all_unique_groups: Vec<Vec<String>> = columns
+            .iter()
+            .filter(|c| c.primary_key || c.unique)
+            .map(|c| vec![c.name.clone()])
+            .collect();
+        all_unique_groups.extend(unique_groups);
+
+        let cols: Vec<ColumnEntry> = columns
             .iter()
-            .map(|c| (c.name.clone(), format!("{:?}", c.data_type)))
+            .map(|c| ColumnEntry {
+                name: c.name.clone(),
+                data_type: format!("{:?}", c.data_type),
+                not_null: c.not_null,
+                primary_key: c.primary_key,
+                unique: c.unique,
+                default: c.default.clone(),
+            })
             .collect();
 
         let entry = CatalogEntry {
             name: name.to_string(),
             columns: cols,
+            indexes: Vec::new(),
+            bitmap_indexes: Vec::new(),
+            unique_groups: all_unique_groups,
+            foreign_keys,
         };
 
         let value = serde_json::to_vec(&entry)?;
         self.storage.set(&key, &value)?;
 
+        for column in &columns {
+            if column.primary_key || column.unique {
+                self.create_index(name, &column.name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers `name` as a temporary table under `TEMP_CATALOG_PREFIX` rather
+    /// than `CATALOG_PREFIX`, so it's kept out of `list_tables` and can be torn
+    /// down in one call via `drop_temp_tables` instead of persisting like a base
+    /// table. Used by `CREATE TEMPORARY TABLE ... AS SELECT`.
+    pub fn create_temp_table(
+        &self,
+        name: &str,
+        columns: Vec<Column>,
+        foreign_keys: Vec<ForeignKey>,
+        unique_groups: Vec<Vec<String>>,
+    ) -> Result<()> {
+        let key = Self::temp_table_key(name);
+
+        if self.storage.get(&key)?.is_some() {
+            return Err(StorageError::WriteError(format!(
+                "Table {} already exists",
+                name
+            )));
+        }
+
+        let mut all_unique_groups: Vec<Vec<String>> = columns
+            .iter()
+            .filter(|c| c.primary_key || c.unique)
+            .map(|c| vec![c.name.clone()])
+            .collect();
+        all_unique_groups.extend(unique_groups);
+
+        let cols: Vec<ColumnEntry> = columns
+            .iter()
+            .map(|c| ColumnEntry {
+                name: c.name.clone(),
+                data_type: format!("{:?}", c.data_type),
+                not_null: c.not_null,
+                primary_key: c.primary_key,
+                unique: c.unique,
+                default: c.default.clone(),
+            })
+            .collect();
+
+        let entry = CatalogEntry {
+            name: name.to_string(),
+            columns: cols,
+            indexes: Vec::new(),
+            bitmap_indexes: Vec::new(),
+            unique_groups: all_unique_groups,
+            foreign_keys,
+        };
+
+        self.storage.set(&key, &serde_json::to_vec(&entry)?)?;
+
         Ok(())
     }
 
     pub fn get_table(&self, name: &str) -> Result<Option<TableSchema>> {
-        let key = Self::table_key(name);
+        if let Some(data) = self.storage.get(&Self::table_key(name))? {
+            return Ok(Some(Self::entry_to_schema(serde_json::from_slice(&data)?)));
+        }
+        if let Some(data) = self.storage.get(&Self::temp_table_key(name))? {
+            return Ok(Some(Self::entry_to_schema(serde_json::from_slice(&data)?)));
+        }
+        Ok(None)
+    }
 
-        if let Some(data) = self.storage.get(&key)? {
-            let entry: CatalogEntry = serde_json::from_slice(&data)?;
-            let columns = entry
-                .columns
-                .iter()
-                .map(|(name, dtype)| Column {
-                    name: name.clone(),
-                    data_type: Self::parse_data_type(dtype),
-                })
-                .collect();
-
-            Ok(Some(TableSchema {
-                name: entry.name,
-                columns,
-            }))
-        } else {
-            Ok(None)
+    /// Live row count for `name`, used by `Planner::explain` to cost a full
+    /// scan from a real number instead of a guess. NexumDB keeps no running
+    /// statistics, so this counts `name`'s data keys directly in storage.
+    pub fn row_count(&self, name: &str) -> Result<usize> {
+        let prefix = format!("data:{}:", name).into_bytes();
+        Ok(self.storage.scan_prefix(&prefix)?.len())
+    }
+
+    fn entry_to_schema(entry: CatalogEntry) -> TableSchema {
+        let columns = entry
+            .columns
+            .iter()
+            .map(|c| Column {
+                name: c.name.clone(),
+                data_type: Self::parse_data_type(&c.data_type),
+                not_null: c.not_null,
+                primary_key: c.primary_key,
+                unique: c.unique,
+                default: c.default.clone(),
+            })
+            .collect();
+
+        TableSchema {
+            name: entry.name,
+            columns,
+            unique_groups: entry.unique_groups,
+            foreign_keys: entry.foreign_keys,
         }
     }
 
+    /// Lists every temporary table registered via `create_temp_table`, kept
+    /// separate from `list_tables` since temp relations aren't base tables.
+    pub fn list_temp_tables(&self) -> Result<Vec<String>> {
+        let results = self.storage.scan_prefix(Self::TEMP_CATALOG_PREFIX)?;
+        let tables = results
+            .iter()
+            .filter_map(|(_, v)| {
+                serde_json::from_slice::<CatalogEntry>(v)
+                    .ok()
+                    .map(|e| e.name)
+            })
+            .collect();
+        Ok(tables)
+    }
+
+    /// Drops every temporary table's catalog entry, as done at session end so
+    /// they don't linger like base tables.
+    pub fn drop_temp_tables(&self) -> Result<()> {
+        for name in self.list_temp_tables()? {
+            self.storage.delete(&Self::temp_table_key(&name))?;
+        }
+        Ok(())
+    }
+
     pub fn list_tables(&self) -> Result<Vec<String>> {
         let results = self.storage.scan_prefix(Self::CATALOG_PREFIX)?;
         let tables = results
@@ -82,8 +250,304 @@ impl Catalog {
     }
 
     pub fn drop_table(&self, name: &str) -> Result<()> {
-        let key = Self::table_key(name);
-        self.storage.delete(&key)?;
+        self.storage.delete(&Self::table_key(name))?;
+        self.storage.delete(&Self::temp_table_key(name))?;
+        Ok(())
+    }
+
+    /// Registers `column` as indexed for `table`. Idempotent: indexing an already-indexed
+    /// column is a no-op. The caller is responsible for backfilling the index data itself.
+    pub fn create_index(&self, table: &str, column: &str) -> Result<()> {
+        let key = Self::table_key(table);
+        let data = self
+            .storage
+            .get(&key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+
+        if !entry.columns.iter().any(|c| c.name == column) {
+            return Err(StorageError::WriteError(format!(
+                "Column {} not found on table {}",
+                column, table
+            )));
+        }
+
+        if !entry.indexes.iter().any(|c| c == column) {
+            entry.indexes.push(column.to_string());
+            let value = serde_json::to_vec(&entry)?;
+            self.storage.set(&key, &value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns index metadata for `column` on `table`, or `None` if no index exists.
+    pub fn index_for_column(&self, table: &str, column: &str) -> Result<Option<IndexInfo>> {
+        let key = Self::table_key(table);
+        if let Some(data) = self.storage.get(&key)? {
+            let entry: CatalogEntry = serde_json::from_slice(&data)?;
+            if entry.indexes.iter().any(|c| c == column) {
+                return Ok(Some(IndexInfo {
+                    table: table.to_string(),
+                    column: column.to_string(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Lists all indexed columns for `table`, used by the executor to keep index
+    /// entries in sync as rows are written.
+    pub fn indexed_columns(&self, table: &str) -> Result<Vec<String>> {
+        let key = Self::table_key(table);
+        if let Some(data) = self.storage.get(&key)? {
+            let entry: CatalogEntry = serde_json::from_slice(&data)?;
+            Ok(entry.indexes)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Registers `column` as backed by a `CREATE INDEX` Roaring-bitmap posting
+    /// list. Idempotent, like `create_index`; the caller backfills the posting
+    /// list itself.
+    pub fn create_bitmap_index(&self, table: &str, column: &str) -> Result<()> {
+        let key = Self::table_key(table);
+        let data = self
+            .storage
+            .get(&key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+
+        if !entry.columns.iter().any(|c| c.name == column) {
+            return Err(StorageError::WriteError(format!(
+                "Column {} not found on table {}",
+                column, table
+            )));
+        }
+
+        if !entry.bitmap_indexes.iter().any(|c| c == column) {
+            entry.bitmap_indexes.push(column.to_string());
+            let value = serde_json::to_vec(&entry)?;
+            self.storage.set(&key, &value)?;
+        }
+
+        Ok(())
+    }
+
+    /// True if `column` is backed by a `CREATE INDEX` Roaring-bitmap posting list.
+    pub fn is_bitmap_indexed(&self, table: &str, column: &str) -> Result<bool> {
+        let key = Self::table_key(table);
+        if let Some(data) = self.storage.get(&key)? {
+            let entry: CatalogEntry = serde_json::from_slice(&data)?;
+            return Ok(entry.bitmap_indexes.iter().any(|c| c == column));
+        }
+        Ok(false)
+    }
+
+    /// Lists all bitmap-indexed columns for `table`, used by the executor to
+    /// keep posting lists in sync as rows are written.
+    pub fn bitmap_indexed_columns(&self, table: &str) -> Result<Vec<String>> {
+        let key = Self::table_key(table);
+        if let Some(data) = self.storage.get(&key)? {
+            let entry: CatalogEntry = serde_json::from_slice(&data)?;
+            Ok(entry.bitmap_indexes)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Finds every `(table, foreign_key)` pair across the catalog whose
+    /// `foreign_key.ref_table` is `table`, so a `DELETE` can enforce the
+    /// referencing side's `ON DELETE` action.
+    pub fn tables_referencing(&self, table: &str) -> Result<Vec<(String, ForeignKey)>> {
+        let results = self.storage.scan_prefix(Self::CATALOG_PREFIX)?;
+        let mut referencing = Vec::new();
+        for (_, v) in &results {
+            if let Ok(entry) = serde_json::from_slice::<CatalogEntry>(v) {
+                for fk in &entry.foreign_keys {
+                    if fk.ref_table == table {
+                        referencing.push((entry.name.clone(), fk.clone()));
+                    }
+                }
+            }
+        }
+        Ok(referencing)
+    }
+
+    /// Adds `column` to `table`'s schema. Existing rows are narrower than the new
+    /// schema; the executor is responsible for treating a missing trailing value
+    /// as `column.default`/`NULL` at read time rather than rewriting stored rows.
+    pub fn add_column(&self, table: &str, column: Column) -> Result<()> {
+        let key = Self::table_key(table);
+        let data = self
+            .storage
+            .get(&key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+
+        if entry.columns.iter().any(|c| c.name == column.name) {
+            return Err(StorageError::WriteError(format!(
+                "Column {} already exists on table {}",
+                column.name, table
+            )));
+        }
+
+        entry.columns.push(ColumnEntry {
+            name: column.name.clone(),
+            data_type: format!("{:?}", column.data_type),
+            not_null: column.not_null,
+            primary_key: column.primary_key,
+            unique: column.unique,
+            default: column.default.clone(),
+        });
+
+        let value = serde_json::to_vec(&entry)?;
+        self.storage.set(&key, &value)?;
+
+        if column.primary_key || column.unique {
+            self.create_index(table, &column.name)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes `name` from `table`'s schema, returning its former column index so
+    /// the executor can splice the matching value out of every stored row.
+    /// Dropping a `PRIMARY KEY` column is rejected, since the row key scheme and
+    /// foreign key enforcement both assume it stays stable and present.
+    pub fn drop_column(&self, table: &str, name: &str) -> Result<usize> {
+        let key = Self::table_key(table);
+        let data = self
+            .storage
+            .get(&key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+
+        let position = entry
+            .columns
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| {
+                StorageError::WriteError(format!("Column {} not found on table {}", name, table))
+            })?;
+
+        if entry.columns[position].primary_key {
+            return Err(StorageError::WriteError(format!(
+                "Cannot drop primary key column {} on table {}",
+                name, table
+            )));
+        }
+
+        entry.columns.remove(position);
+        entry.indexes.retain(|c| c != name);
+        entry.bitmap_indexes.retain(|c| c != name);
+        entry.unique_groups.retain(|group| !group.iter().any(|c| c == name));
+
+        let value = serde_json::to_vec(&entry)?;
+        self.storage.set(&key, &value)?;
+
+        Ok(position)
+    }
+
+    /// Renames a column in place, updating its entries in `indexes`,
+    /// `bitmap_indexes`, and `unique_groups` so they keep pointing at the right
+    /// column. The executor is responsible for re-keying any physical index
+    /// storage under the old name.
+    pub fn rename_column(&self, table: &str, old_name: &str, new_name: &str) -> Result<()> {
+        let key = Self::table_key(table);
+        let data = self
+            .storage
+            .get(&key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+
+        if entry.columns.iter().any(|c| c.name == new_name) {
+            return Err(StorageError::WriteError(format!(
+                "Column {} already exists on table {}",
+                new_name, table
+            )));
+        }
+
+        let column = entry
+            .columns
+            .iter_mut()
+            .find(|c| c.name == old_name)
+            .ok_or_else(|| {
+                StorageError::WriteError(format!(
+                    "Column {} not found on table {}",
+                    old_name, table
+                ))
+            })?;
+        column.name = new_name.to_string();
+
+        for indexed in entry.indexes.iter_mut() {
+            if indexed == old_name {
+                *indexed = new_name.to_string();
+            }
+        }
+        for indexed in entry.bitmap_indexes.iter_mut() {
+            if indexed == old_name {
+                *indexed = new_name.to_string();
+            }
+        }
+        for group in entry.unique_groups.iter_mut() {
+            for member in group.iter_mut() {
+                if member == old_name {
+                    *member = new_name.to_string();
+                }
+            }
+        }
+
+        let value = serde_json::to_vec(&entry)?;
+        self.storage.set(&key, &value)?;
+
+        Ok(())
+    }
+
+    /// Renames `table` itself, re-keying its catalog entry and fixing up any
+    /// other table's `FOREIGN KEY ... REFERENCES` that pointed at the old name.
+    /// The executor is responsible for re-keying the physical row/index storage.
+    pub fn rename_table(&self, old_name: &str, new_name: &str) -> Result<()> {
+        let old_key = Self::table_key(old_name);
+        let new_key = Self::table_key(new_name);
+
+        let data = self
+            .storage
+            .get(&old_key)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", old_name)))?;
+        if self.storage.get(&new_key)?.is_some() {
+            return Err(StorageError::WriteError(format!(
+                "Table {} already exists",
+                new_name
+            )));
+        }
+
+        let mut entry: CatalogEntry = serde_json::from_slice(&data)?;
+        entry.name = new_name.to_string();
+        self.storage.set(&new_key, &serde_json::to_vec(&entry)?)?;
+        self.storage.delete(&old_key)?;
+
+        for table in self.list_tables()? {
+            if table == new_name {
+                continue;
+            }
+            let key = Self::table_key(&table);
+            if let Some(data) = self.storage.get(&key)? {
+                let mut other: CatalogEntry = serde_json::from_slice(&data)?;
+                let mut changed = false;
+                for fk in other.foreign_keys.iter_mut() {
+                    if fk.ref_table == old_name {
+                        fk.ref_table = new_name.to_string();
+                        changed = true;
+                    }
+                }
+                if changed {
+                    self.storage.set(&key, &serde_json::to_vec(&other)?)?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -93,6 +557,12 @@ impl Catalog {
         key
     }
 
+    fn temp_table_key(name: &str) -> Vec<u8> {
+        let mut key = Self::TEMP_CATALOG_PREFIX.to_vec();
+        key.extend_from_slice(name.as_bytes());
+        key
+    }
+
     fn parse_data_type(s: &str) -> crate::sql::types::DataType {
         use crate::sql::types::DataType;
         match s {
@@ -100,6 +570,7 @@ impl Catalog {
             "Float" => DataType::Float,
             "Text" => DataType::Text,
             "Boolean" => DataType::Boolean,
+            "Json" => DataType::Json,
             _ => DataType::Null,
         }
     }
@@ -117,17 +588,11 @@ mod tests {
         let catalog = Catalog::new(storage);
 
         let columns = vec![
-            Column {
-                name: "id".to_string(),
-                data_type: DataType::Integer,
-            },
-            Column {
-                name: "name".to_string(),
-                data_type: DataType::Text,
-            },
+            Column::new("id", DataType::Integer),
+            Column::new("name", DataType::Text),
         ];
 
-        catalog.create_table("users", columns).unwrap();
+        catalog.create_table("users", columns, vec![], vec![]).unwrap();
 
         let schema = catalog.get_table("users").unwrap();
         assert!(schema.is_some());
@@ -141,6 +606,58 @@ mod tests {
         assert_eq!(tables[0], "users");
     }
 
+    #[test]
+    fn test_create_index_and_lookup() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        let columns = vec![
+            Column::new("id", DataType::Integer),
+            Column::new("name", DataType::Text),
+        ];
+        catalog.create_table("users", columns, vec![], vec![]).unwrap();
+
+        assert!(catalog.index_for_column("users", "id").unwrap().is_none());
+
+        catalog.create_index("users", "id").unwrap();
+
+        let info = catalog.index_for_column("users", "id").unwrap().unwrap();
+        assert_eq!(info.table, "users");
+        assert_eq!(info.column, "id");
+        assert_eq!(catalog.indexed_columns("users").unwrap(), vec!["id".to_string()]);
+
+        assert!(catalog.create_index("users", "missing").is_err());
+    }
+
+    #[test]
+    fn test_create_bitmap_index_and_lookup() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        let columns = vec![
+            Column::new("id", DataType::Integer),
+            Column::new("age", DataType::Integer),
+        ];
+        catalog.create_table("users", columns, vec![], vec![]).unwrap();
+
+        assert!(!catalog.is_bitmap_indexed("users", "age").unwrap());
+
+        catalog.create_bitmap_index("users", "age").unwrap();
+
+        assert!(catalog.is_bitmap_indexed("users", "age").unwrap());
+        assert_eq!(
+            catalog.bitmap_indexed_columns("users").unwrap(),
+            vec!["age".to_string()]
+        );
+
+        // Idempotent, and independent of the byte-range `indexes` list.
+        catalog.create_bitmap_index("users", "age").unwrap();
+        assert_eq!(catalog.bitmap_indexed_columns("users").unwrap().len(), 1);
+        assert!(catalog.index_for_column("users", "age").unwrap().is_none());
+
+        assert!(catalog.create_bitmap_index("users", "missing").is_err());
+    }
+
     #[test]
     fn test_catalog_persistence() {
         let temp_dir = tempdir().unwrap();
@@ -150,12 +667,11 @@ mod tests {
             let storage = StorageEngine::new(&db_path).unwrap();
             let catalog = Catalog::new(storage);
 
-            let columns = vec![Column {
-                name: "id".to_string(),
-                data_type: DataType::Integer,
-            }];
+            let columns = vec![Column::new("id", DataType::Integer)];
 
-            catalog.create_table("persist_table", columns).unwrap();
+            catalog
+                .create_table("persist_table", columns, vec![], vec![])
+                .unwrap();
         }
 
         {
@@ -170,4 +686,160 @@ mod tests {
             assert_eq!(tables, vec!["persist_table".to_string()]);
         }
     }
+
+    #[test]
+    fn test_constraints_persist_and_auto_index_unique_columns() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        id_col.unique = true;
+        let mut name_col = Column::new("name", DataType::Text);
+        name_col.not_null = true;
+        let mut status_col = Column::new("status", DataType::Text);
+        status_col.default = Some(Value::Text("active".to_string()));
+
+        catalog
+            .create_table(
+                "users",
+                vec![id_col, name_col, status_col],
+                vec![ForeignKey {
+                    columns: vec!["id".to_string()],
+                    ref_table: "accounts".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+                vec![],
+            )
+            .unwrap();
+
+        // PRIMARY KEY/UNIQUE columns are automatically backed by an index.
+        assert!(catalog.index_for_column("users", "id").unwrap().is_some());
+
+        let schema = catalog.get_table("users").unwrap().unwrap();
+        assert_eq!(schema.unique_groups, vec![vec!["id".to_string()]]);
+        assert!(schema.columns[1].not_null);
+        assert_eq!(
+            schema.columns[2].default,
+            Some(Value::Text("active".to_string()))
+        );
+        assert_eq!(schema.foreign_keys[0].ref_table, "accounts");
+
+        let referencing = catalog.tables_referencing("accounts").unwrap();
+        assert_eq!(referencing.len(), 1);
+        assert_eq!(referencing[0].0, "users");
+    }
+
+    #[test]
+    fn test_add_rename_and_drop_column() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        let columns = vec![
+            Column::new("id", DataType::Integer),
+            Column::new("name", DataType::Text),
+        ];
+        catalog.create_table("users", columns, vec![], vec![]).unwrap();
+
+        catalog.add_column("users", Column::new("age", DataType::Integer)).unwrap();
+        let schema = catalog.get_table("users").unwrap().unwrap();
+        assert_eq!(schema.columns.len(), 3);
+        assert_eq!(schema.columns[2].name, "age");
+
+        // Adding a duplicate column name is rejected.
+        assert!(catalog
+            .add_column("users", Column::new("age", DataType::Text))
+            .is_err());
+
+        catalog.rename_column("users", "name", "full_name").unwrap();
+        let schema = catalog.get_table("users").unwrap().unwrap();
+        assert_eq!(schema.columns[1].name, "full_name");
+
+        let dropped_at = catalog.drop_column("users", "full_name").unwrap();
+        assert_eq!(dropped_at, 1);
+        let schema = catalog.get_table("users").unwrap().unwrap();
+        assert_eq!(schema.columns.len(), 2);
+        assert_eq!(schema.columns[1].name, "age");
+    }
+
+    #[test]
+    fn test_drop_primary_key_column_rejected() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        catalog
+            .create_table("users", vec![id_col, Column::new("name", DataType::Text)], vec![], vec![])
+            .unwrap();
+
+        assert!(catalog.drop_column("users", "id").is_err());
+    }
+
+    #[test]
+    fn test_rename_table_updates_referencing_foreign_keys() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        catalog
+            .create_table("accounts", vec![Column::new("id", DataType::Integer)], vec![], vec![])
+            .unwrap();
+        catalog
+            .create_table(
+                "orders",
+                vec![Column::new("account_id", DataType::Integer)],
+                vec![ForeignKey {
+                    columns: vec!["account_id".to_string()],
+                    ref_table: "accounts".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::NoAction,
+                }],
+                vec![],
+            )
+            .unwrap();
+
+        catalog.rename_table("accounts", "customers").unwrap();
+
+        assert!(catalog.get_table("accounts").unwrap().is_none());
+        assert!(catalog.get_table("customers").unwrap().is_some());
+
+        let schema = catalog.get_table("orders").unwrap().unwrap();
+        assert_eq!(schema.foreign_keys[0].ref_table, "customers");
+    }
+
+    #[test]
+    fn test_temp_table_hidden_from_list_tables_and_cleared_in_bulk() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+
+        catalog
+            .create_table("users", vec![Column::new("id", DataType::Integer)], vec![], vec![])
+            .unwrap();
+        catalog
+            .create_temp_table(
+                "recent_users",
+                vec![Column::new("id", DataType::Integer)],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+
+        // Temp tables are findable by name but don't show up in list_tables.
+        assert!(catalog.get_table("recent_users").unwrap().is_some());
+        assert_eq!(catalog.list_tables().unwrap(), vec!["users".to_string()]);
+        assert_eq!(
+            catalog.list_temp_tables().unwrap(),
+            vec!["recent_users".to_string()]
+        );
+
+        catalog.drop_temp_tables().unwrap();
+
+        assert!(catalog.get_table("recent_users").unwrap().is_none());
+        assert!(catalog.get_table("users").unwrap().is_some());
+    }
 }
@@ -0,0 +1,271 @@
+//! `u32` row-ids and Roaring-bitmap-backed posting lists for `CREATE INDEX`.
+//!
+//! Unlike the byte-range `idx:` index in `executor::index` (one key per row),
+//! every distinct value here owns a single `index:{table}:{column}:{encoded_value}`
+//! key holding a serialized `RoaringBitmap` of matching row-ids, so combining
+//! several indexed predicates is a handful of bitmap unions/intersections
+//! instead of merging key ranges. A separate `rowid:`/`rowid_rev:` keyspace maps
+//! row-ids to (and from) the row key used by `Executor::generate_row_key`, so a
+//! resolved bitmap can be turned back into actual rows.
+
+use super::index::encode_value;
+use crate::sql::types::Value;
+use crate::storage::{Result, StorageEngine, StorageError};
+use roaring::RoaringBitmap;
+
+const SEQ_PREFIX: &[u8] = b"rowid_seq:";
+const ROWID_PREFIX: &[u8] = b"rowid:";
+const ROWID_REV_PREFIX: &[u8] = b"rowid_rev:";
+const POSTING_PREFIX: &[u8] = b"index:";
+
+/// `rowid_seq:{table}` — the single key holding the table's next row-id.
+pub fn seq_key(table: &str) -> Vec<u8> {
+    let mut key = SEQ_PREFIX.to_vec();
+    key.extend_from_slice(table.as_bytes());
+    key
+}
+
+/// `rowid:{table}:` — the prefix of every row-id -> row-key mapping for `table`.
+pub fn rowid_prefix(table: &str) -> Vec<u8> {
+    let mut key = ROWID_PREFIX.to_vec();
+    key.extend_from_slice(table.as_bytes());
+    key.push(b':');
+    key
+}
+
+/// `rowid_rev:{table}:` — the prefix of every row-key -> row-id mapping for `table`.
+pub fn rowid_rev_prefix(table: &str) -> Vec<u8> {
+    let mut key = ROWID_REV_PREFIX.to_vec();
+    key.extend_from_slice(table.as_bytes());
+    key.push(b':');
+    key
+}
+
+fn rowid_key(table: &str, row_id: u32) -> Vec<u8> {
+    let mut key = rowid_prefix(table);
+    key.extend_from_slice(&row_id.to_be_bytes());
+    key
+}
+
+fn rowid_rev_key(table: &str, row_key: &[u8]) -> Vec<u8> {
+    let mut key = rowid_rev_prefix(table);
+    key.extend_from_slice(row_key);
+    key
+}
+
+/// `index:{table}:{column}:` — the prefix shared by every posting list of this index.
+pub fn posting_prefix(table: &str, column: &str) -> Vec<u8> {
+    let mut key = POSTING_PREFIX.to_vec();
+    key.extend_from_slice(table.as_bytes());
+    key.push(b':');
+    key.extend_from_slice(column.as_bytes());
+    key.push(b':');
+    key
+}
+
+fn posting_key(table: &str, column: &str, value: &Value) -> Option<Vec<u8>> {
+    let mut key = posting_prefix(table, column);
+    key.extend_from_slice(&encode_value(value)?);
+    Some(key)
+}
+
+/// Allocates the next monotonically increasing row-id for `table` and records
+/// the row-id <-> row-key mapping in both directions, so a later `CREATE INDEX`
+/// can backfill in insertion order and a delete can find which posting lists to
+/// clear. Every insert gets a row-id, independent of whether `table` currently
+/// has any bitmap index.
+pub fn assign_row_id(storage: &StorageEngine, table: &str, row_key: &[u8]) -> Result<u32> {
+    let key = seq_key(table);
+    let row_id = match storage.get(&key)? {
+        Some(bytes) => {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&bytes);
+            u32::from_be_bytes(buf).wrapping_add(1)
+        }
+        None => 0,
+    };
+    storage.set(&key, &row_id.to_be_bytes())?;
+    storage.set(&rowid_key(table, row_id), row_key)?;
+    storage.set(&rowid_rev_key(table, row_key), &row_id.to_be_bytes())?;
+    Ok(row_id)
+}
+
+/// Looks up the row-id assigned to `row_key` by `assign_row_id`, if any.
+pub fn row_id_for_key(storage: &StorageEngine, table: &str, row_key: &[u8]) -> Result<Option<u32>> {
+    match storage.get(&rowid_rev_key(table, row_key))? {
+        Some(bytes) => {
+            let mut buf = [0u8; 4];
+            buf.copy_from_slice(&bytes);
+            Ok(Some(u32::from_be_bytes(buf)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Looks up the row key a row-id was assigned to.
+pub fn row_key_for_id(storage: &StorageEngine, table: &str, row_id: u32) -> Result<Option<Vec<u8>>> {
+    storage.get(&rowid_key(table, row_id))
+}
+
+/// Clears a row-id's forward/reverse key mapping once its row is gone, so a
+/// stale row-id removed from every posting list is never resolved back into a
+/// deleted row.
+pub fn forget_row_id(storage: &StorageEngine, table: &str, row_id: u32, row_key: &[u8]) -> Result<()> {
+    storage.delete(&rowid_key(table, row_id))?;
+    storage.delete(&rowid_rev_key(table, row_key))?;
+    Ok(())
+}
+
+fn load_bitmap(storage: &StorageEngine, table: &str, column: &str, value: &Value) -> Result<RoaringBitmap> {
+    let Some(key) = posting_key(table, column, value) else {
+        return Ok(RoaringBitmap::new());
+    };
+    match storage.get(&key)? {
+        Some(bytes) => RoaringBitmap::deserialize_from(&bytes[..])
+            .map_err(|e| StorageError::ReadError(format!("Corrupt index bitmap: {}", e))),
+        None => Ok(RoaringBitmap::new()),
+    }
+}
+
+fn save_bitmap(
+    storage: &StorageEngine,
+    table: &str,
+    column: &str,
+    value: &Value,
+    bitmap: &RoaringBitmap,
+) -> Result<()> {
+    let Some(key) = posting_key(table, column, value) else {
+        return Ok(());
+    };
+    if bitmap.is_empty() {
+        storage.delete(&key)?;
+        return Ok(());
+    }
+    let mut bytes = Vec::new();
+    bitmap
+        .serialize_into(&mut bytes)
+        .map_err(|e| StorageError::WriteError(format!("Failed to serialize index bitmap: {}", e)))?;
+    storage.set(&key, &bytes)
+}
+
+/// Adds `row_id` to `value`'s posting list, creating the list if this is the
+/// value's first row. A no-op for values with no order-preserving encoding
+/// (`NULL`, `Json`), which are simply excluded from the index.
+pub fn add_row(storage: &StorageEngine, table: &str, column: &str, value: &Value, row_id: u32) -> Result<()> {
+    let mut bitmap = load_bitmap(storage, table, column, value)?;
+    bitmap.insert(row_id);
+    save_bitmap(storage, table, column, value, &bitmap)
+}
+
+/// Removes `row_id` from `value`'s posting list, deleting the list entirely
+/// once it empties out rather than leaving a dangling empty bitmap behind.
+pub fn remove_row(storage: &StorageEngine, table: &str, column: &str, value: &Value, row_id: u32) -> Result<()> {
+    let mut bitmap = load_bitmap(storage, table, column, value)?;
+    bitmap.remove(row_id);
+    save_bitmap(storage, table, column, value, &bitmap)
+}
+
+/// The posting list for a single value, used to resolve a `=` predicate (and as
+/// one term of an `IN` union). `None` if `value` has no order-preserving
+/// encoding, signaling the caller to fall back to a full scan.
+pub fn eq_bitmap(
+    storage: &StorageEngine,
+    table: &str,
+    column: &str,
+    value: &Value,
+) -> Result<Option<RoaringBitmap>> {
+    if encode_value(value).is_none() {
+        return Ok(None);
+    }
+    Ok(Some(load_bitmap(storage, table, column, value)?))
+}
+
+/// Unions every posting list whose encoded value falls within `[low, high]`
+/// (inclusive), used to resolve a `BETWEEN` predicate without decoding every
+/// key's value back out. `None` if either bound has no order-preserving encoding.
+pub fn range_union(
+    storage: &StorageEngine,
+    table: &str,
+    column: &str,
+    low: &Value,
+    high: &Value,
+) -> Result<Option<RoaringBitmap>> {
+    let (Some(low_enc), Some(high_enc)) = (encode_value(low), encode_value(high)) else {
+        return Ok(None);
+    };
+
+    let prefix = posting_prefix(table, column);
+    let mut start = prefix.clone();
+    start.extend_from_slice(&low_enc);
+    let mut end = prefix;
+    end.extend_from_slice(&high_enc);
+    end.push(0xFF);
+
+    let mut union = RoaringBitmap::new();
+    for (_, bytes) in storage.scan_range(start..end)? {
+        let bitmap = RoaringBitmap::deserialize_from(&bytes[..])
+            .map_err(|e| StorageError::ReadError(format!("Corrupt index bitmap: {}", e)))?;
+        union |= bitmap;
+    }
+    Ok(Some(union))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assign_row_id_is_monotonic_and_bidirectional() {
+        let storage = StorageEngine::memory().unwrap();
+        let a = assign_row_id(&storage, "users", b"rowkey-a").unwrap();
+        let b = assign_row_id(&storage, "users", b"rowkey-b").unwrap();
+
+        assert_eq!(a, 0);
+        assert_eq!(b, 1);
+        assert_eq!(row_id_for_key(&storage, "users", b"rowkey-a").unwrap(), Some(0));
+        assert_eq!(
+            row_key_for_id(&storage, "users", 1).unwrap(),
+            Some(b"rowkey-b".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_add_and_remove_row_from_posting_list() {
+        let storage = StorageEngine::memory().unwrap();
+        let value = Value::Integer(42);
+
+        add_row(&storage, "users", "age", &value, 0).unwrap();
+        add_row(&storage, "users", "age", &value, 5).unwrap();
+
+        let bitmap = eq_bitmap(&storage, "users", "age", &value).unwrap().unwrap();
+        assert!(bitmap.contains(0));
+        assert!(bitmap.contains(5));
+
+        remove_row(&storage, "users", "age", &value, 0).unwrap();
+        let bitmap = eq_bitmap(&storage, "users", "age", &value).unwrap().unwrap();
+        assert!(!bitmap.contains(0));
+        assert!(bitmap.contains(5));
+    }
+
+    #[test]
+    fn test_range_union_spans_multiple_values() {
+        let storage = StorageEngine::memory().unwrap();
+        add_row(&storage, "users", "age", &Value::Integer(10), 1).unwrap();
+        add_row(&storage, "users", "age", &Value::Integer(20), 2).unwrap();
+        add_row(&storage, "users", "age", &Value::Integer(30), 3).unwrap();
+
+        let union = range_union(&storage, "users", "age", &Value::Integer(10), &Value::Integer(20))
+            .unwrap()
+            .unwrap();
+
+        assert!(union.contains(1));
+        assert!(union.contains(2));
+        assert!(!union.contains(3));
+    }
+
+    #[test]
+    fn test_null_value_has_no_posting_list() {
+        let storage = StorageEngine::memory().unwrap();
+        assert_eq!(eq_bitmap(&storage, "users", "age", &Value::Null).unwrap(), None);
+    }
+}
@@ -1,18 +1,51 @@
+use super::SubqueryRunner;
 use crate::sql::types::Value;
 use anyhow::{anyhow, Result};
-use regex::Regex;
-use sqlparser::ast::{BinaryOperator, Expr, Value as SqlValue};
+use sqlparser::ast::{
+    BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, Ident, Query, Value as SqlValue,
+};
 
-pub struct ExpressionEvaluator {
+/// One piece of a tokenized `LIKE` pattern: a literal run of characters, a
+/// single-character wildcard (`_`), or a zero-or-more wildcard (`%`).
+enum LikeToken {
+    Literal(String),
+    Any,
+    Many,
+}
+
+pub struct ExpressionEvaluator<'a> {
     column_names: Vec<String>,
+    subquery_runner: Option<&'a dyn SubqueryRunner>,
 }
 
-impl ExpressionEvaluator {
+impl<'a> ExpressionEvaluator<'a> {
     pub fn new(column_names: Vec<String>) -> Self {
-        Self { column_names }
+        Self {
+            column_names,
+            subquery_runner: None,
+        }
+    }
+
+    /// Attaches a `SubqueryRunner` so `evaluate` can materialize `IN (SELECT ...)`,
+    /// `EXISTS (...)`, and `= ANY/ALL (...)` subqueries, correlating them against
+    /// the outer row via this evaluator's column names.
+    pub fn with_runner(column_names: Vec<String>, runner: &'a dyn SubqueryRunner) -> Self {
+        Self {
+            column_names,
+            subquery_runner: Some(runner),
+        }
     }
 
-    pub fn evaluate(&self, expr: &Expr, row_values: &[Value]) -> Result<bool> {
+    fn require_runner(&self) -> Result<&'a dyn SubqueryRunner> {
+        self.subquery_runner
+            .ok_or_else(|| anyhow!("Subquery expression used without a SubqueryRunner"))
+    }
+
+    /// Evaluates `expr` to SQL's three-valued logic: `Some(true)`/`Some(false)` for a
+    /// definite result, `None` for UNKNOWN (produced whenever a NULL participates in a
+    /// comparison). Callers implementing WHERE must keep only rows where this returns
+    /// `Ok(Some(true))`.
+    pub fn evaluate(&self, expr: &Expr, row_values: &[Value]) -> Result<Option<bool>> {
         match expr {
             Expr::BinaryOp { left, op, right } => {
                 self.evaluate_binary_op(left, op, right, row_values)
@@ -26,16 +59,45 @@ impl ExpressionEvaluator {
                     .ok_or_else(|| anyhow!("Column {} not found", col_name))?;
 
                 match &row_values[idx] {
-                    Value::Boolean(b) => Ok(*b),
+                    Value::Boolean(b) => Ok(Some(*b)),
+                    Value::Null => Ok(None),
                     _ => Err(anyhow!("Expected boolean value for identifier")),
                 }
             }
+            Expr::IsNull(inner) => {
+                let value = self.extract_value(inner, row_values)?;
+                Ok(Some(value == Value::Null))
+            }
+            Expr::IsNotNull(inner) => {
+                let value = self.extract_value(inner, row_values)?;
+                Ok(Some(value != Value::Null))
+            }
             Expr::Like {
                 negated,
                 expr,
                 pattern,
                 escape_char,
-            } => self.evaluate_like(*negated, expr, pattern, escape_char.as_ref(), row_values),
+            } => self.evaluate_like(
+                *negated,
+                expr,
+                pattern,
+                escape_char.as_ref(),
+                false,
+                row_values,
+            ),
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => self.evaluate_like(
+                *negated,
+                expr,
+                pattern,
+                escape_char.as_ref(),
+                true,
+                row_values,
+            ),
             Expr::InList {
                 expr,
                 list,
@@ -47,27 +109,146 @@ impl ExpressionEvaluator {
                 low,
                 high,
             } => self.evaluate_between(expr, *negated, low, high, row_values),
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => self.evaluate_in_subquery(expr, subquery, *negated, row_values),
+            Expr::Exists { subquery, negated } => {
+                let exists = self.require_runner()?.subquery_has_rows(
+                    subquery,
+                    &self.column_names,
+                    row_values,
+                )?;
+                Ok(Some(if *negated { !exists } else { exists }))
+            }
+            Expr::AnyOp {
+                left,
+                compare_op,
+                right,
+                ..
+            } => self.evaluate_any_all(left, compare_op, right, row_values, true),
+            Expr::AllOp {
+                left,
+                compare_op,
+                right,
+            } => self.evaluate_any_all(left, compare_op, right, row_values, false),
             _ => Err(anyhow!("Unsupported expression type: {:?}", expr)),
         }
     }
 
+    /// `x IN (subquery)` is sugar for `x = ANY(subquery)`: true if `x` matches any
+    /// returned row, UNKNOWN if it matches none but a NULL was returned, else false.
+    fn evaluate_in_subquery(
+        &self,
+        expr: &Expr,
+        subquery: &Query,
+        negated: bool,
+        row_values: &[Value],
+    ) -> Result<Option<bool>> {
+        let value = self.extract_value(expr, row_values)?;
+
+        let candidates =
+            self.require_runner()?
+                .run_subquery(subquery, &self.column_names, row_values)?;
+        // Like `= ANY` over an empty set, an empty subquery is false even for NULL `x`.
+        if candidates.is_empty() {
+            return Ok(Some(negated));
+        }
+        if matches!(value, Value::Null) {
+            return Ok(None);
+        }
+
+        let mut saw_null = false;
+        let mut found = false;
+        for candidate in &candidates {
+            if matches!(candidate, Value::Null) {
+                saw_null = true;
+            } else if self.values_equal(&value, candidate) {
+                found = true;
+                break;
+            }
+        }
+
+        let result = if found {
+            Some(true)
+        } else if saw_null {
+            None
+        } else {
+            Some(false)
+        };
+
+        Ok(if negated { result.map(|b| !b) } else { result })
+    }
+
+    /// `x op ANY(subquery)` is true if the comparison holds for at least one
+    /// returned row (an OR-reduce); `x op ALL(subquery)` is true if it holds for
+    /// every row, vacuously true over an empty result set (an AND-reduce). Both
+    /// fold through `kleene_and`/`kleene_or` so a NULL candidate yields UNKNOWN
+    /// rather than being silently skipped.
+    fn evaluate_any_all(
+        &self,
+        left: &Expr,
+        op: &BinaryOperator,
+        right: &Expr,
+        row_values: &[Value],
+        is_any: bool,
+    ) -> Result<Option<bool>> {
+        let left_val = self.extract_value(left, row_values)?;
+        let subquery = Self::subquery_of(right)?;
+        let candidates =
+            self.require_runner()?
+                .run_subquery(subquery, &self.column_names, row_values)?;
+
+        if candidates.is_empty() {
+            // ANY over an empty set is false; ALL over an empty set is vacuously true.
+            return Ok(Some(!is_any));
+        }
+
+        if matches!(left_val, Value::Null) {
+            return Ok(None);
+        }
+
+        let mut acc = if is_any { Some(false) } else { Some(true) };
+        for candidate in &candidates {
+            let term = if matches!(candidate, Value::Null) {
+                None
+            } else {
+                Some(self.compare_values(&left_val, op, candidate)?)
+            };
+            acc = if is_any {
+                Self::kleene_or(acc, term)
+            } else {
+                Self::kleene_and(acc, term)
+            };
+        }
+        Ok(acc)
+    }
+
+    fn subquery_of(expr: &Expr) -> Result<&Query> {
+        match expr {
+            Expr::Subquery(query) => Ok(query),
+            _ => Err(anyhow!("Expected a subquery, found {:?}", expr)),
+        }
+    }
+
     fn evaluate_binary_op(
         &self,
         left: &Expr,
         op: &BinaryOperator,
         right: &Expr,
         row_values: &[Value],
-    ) -> Result<bool> {
+    ) -> Result<Option<bool>> {
         match op {
             BinaryOperator::And => {
                 let left_result = self.evaluate(left, row_values)?;
                 let right_result = self.evaluate(right, row_values)?;
-                Ok(left_result && right_result)
+                Ok(Self::kleene_and(left_result, right_result))
             }
             BinaryOperator::Or => {
                 let left_result = self.evaluate(left, row_values)?;
                 let right_result = self.evaluate(right, row_values)?;
-                Ok(left_result || right_result)
+                Ok(Self::kleene_or(left_result, right_result))
             }
             BinaryOperator::Gt
             | BinaryOperator::Lt
@@ -77,12 +258,202 @@ impl ExpressionEvaluator {
             | BinaryOperator::NotEq => {
                 let left_val = self.extract_value(left, row_values)?;
                 let right_val = self.extract_value(right, row_values)?;
-                self.compare_values(&left_val, op, &right_val)
+
+                if left_val == Value::Null || right_val == Value::Null {
+                    return Ok(None);
+                }
+
+                Ok(Some(self.compare_values(&left_val, op, &right_val)?))
             }
             _ => Err(anyhow!("Unsupported operator: {:?}", op)),
         }
     }
 
+    /// `AND` is `FALSE` if either side is `FALSE`, else `UNKNOWN` if either side is
+    /// `UNKNOWN`, else `TRUE`.
+    fn kleene_and(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+        match (left, right) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (None, _) | (_, None) => None,
+            (Some(true), Some(true)) => Some(true),
+        }
+    }
+
+    /// `OR` is `TRUE` if either side is `TRUE`, else `UNKNOWN` if either side is
+    /// `UNKNOWN`, else `FALSE`.
+    fn kleene_or(left: Option<bool>, right: Option<bool>) -> Option<bool> {
+        match (left, right) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (None, _) | (_, None) => None,
+            (Some(false), Some(false)) => Some(false),
+        }
+    }
+
+    /// Evaluates `expr` to a scalar `Value` for a projected SELECT column, handling
+    /// arithmetic, string concatenation, and the small built-in function set
+    /// (`UPPER`, `LOWER`, `LENGTH`, `ABS`, `COALESCE`). This is the projection-side
+    /// counterpart to `evaluate`, which only ever produces a boolean/UNKNOWN result.
+    pub fn evaluate_scalar(&self, expr: &Expr, row_values: &[Value]) -> Result<Value> {
+        match expr {
+            Expr::Identifier(ident) => {
+                let col_name = ident.value.as_str();
+                let idx = self
+                    .column_names
+                    .iter()
+                    .position(|name| name == col_name)
+                    .ok_or_else(|| anyhow!("Column {} not found", col_name))?;
+                Ok(row_values[idx].clone())
+            }
+            Expr::Value(sql_val) => self.convert_sql_value(sql_val),
+            Expr::Nested(inner) => self.evaluate_scalar(inner, row_values),
+            Expr::CompoundIdentifier(idents) => self.extract_compound_identifier(idents, row_values),
+            Expr::BinaryOp { left, op, right }
+                if matches!(op, BinaryOperator::Arrow | BinaryOperator::LongArrow) =>
+            {
+                self.extract_json_path(left, op, right, row_values)
+            }
+            Expr::BinaryOp { left, op, right } => {
+                let left_val = self.evaluate_scalar(left, row_values)?;
+                let right_val = self.evaluate_scalar(right, row_values)?;
+                self.evaluate_arithmetic(&left_val, op, &right_val)
+            }
+            Expr::Function(function) => self.evaluate_function(function, row_values),
+            _ => Err(anyhow!("Unsupported scalar expression: {:?}", expr)),
+        }
+    }
+
+    fn evaluate_arithmetic(&self, left: &Value, op: &BinaryOperator, right: &Value) -> Result<Value> {
+        if matches!(op, BinaryOperator::StringConcat) {
+            return match (left, right) {
+                (Value::Text(l), Value::Text(r)) => Ok(Value::Text(format!("{}{}", l, r))),
+                (Value::Null, _) | (_, Value::Null) => Ok(Value::Null),
+                _ => Err(anyhow!("String concatenation requires text operands")),
+            };
+        }
+
+        if matches!(left, Value::Null) || matches!(right, Value::Null) {
+            return Ok(Value::Null);
+        }
+
+        match (left, right) {
+            (Value::Integer(l), Value::Integer(r)) => {
+                let (l, r) = (*l, *r);
+                Ok(Value::Integer(match op {
+                    BinaryOperator::Plus => l + r,
+                    BinaryOperator::Minus => l - r,
+                    BinaryOperator::Multiply => l * r,
+                    BinaryOperator::Divide => {
+                        if r == 0 {
+                            return Err(anyhow!("Division by zero"));
+                        }
+                        l / r
+                    }
+                    BinaryOperator::Modulo => {
+                        if r == 0 {
+                            return Err(anyhow!("Division by zero"));
+                        }
+                        l % r
+                    }
+                    _ => return Err(anyhow!("Unsupported arithmetic operator: {:?}", op)),
+                }))
+            }
+            (Value::Text(l), Value::Text(r)) if matches!(op, BinaryOperator::Plus) => {
+                Ok(Value::Text(format!("{}{}", l, r)))
+            }
+            (Value::Integer(_) | Value::Float(_), Value::Integer(_) | Value::Float(_)) => {
+                let l = Self::as_f64(left)?;
+                let r = Self::as_f64(right)?;
+                Ok(Value::Float(match op {
+                    BinaryOperator::Plus => l + r,
+                    BinaryOperator::Minus => l - r,
+                    BinaryOperator::Multiply => l * r,
+                    BinaryOperator::Divide => {
+                        if r == 0.0 {
+                            return Err(anyhow!("Division by zero"));
+                        }
+                        l / r
+                    }
+                    BinaryOperator::Modulo => {
+                        if r == 0.0 {
+                            return Err(anyhow!("Division by zero"));
+                        }
+                        l % r
+                    }
+                    _ => return Err(anyhow!("Unsupported arithmetic operator: {:?}", op)),
+                }))
+            }
+            _ => Err(anyhow!(
+                "Unsupported operand types for arithmetic: {:?} {:?}",
+                left,
+                right
+            )),
+        }
+    }
+
+    fn as_f64(value: &Value) -> Result<f64> {
+        match value {
+            Value::Integer(n) => Ok(*n as f64),
+            Value::Float(f) => Ok(*f),
+            _ => Err(anyhow!("Expected a numeric value, found {:?}", value)),
+        }
+    }
+
+    fn evaluate_function(&self, function: &Function, row_values: &[Value]) -> Result<Value> {
+        let name = function.name.to_string().to_uppercase();
+        let args = Self::function_arg_exprs(function)?;
+
+        match name.as_str() {
+            "UPPER" => match self.evaluate_scalar(Self::arg(&args, 0)?, row_values)? {
+                Value::Text(s) => Ok(Value::Text(s.to_uppercase())),
+                Value::Null => Ok(Value::Null),
+                other => Err(anyhow!("UPPER requires a text argument, found {:?}", other)),
+            },
+            "LOWER" => match self.evaluate_scalar(Self::arg(&args, 0)?, row_values)? {
+                Value::Text(s) => Ok(Value::Text(s.to_lowercase())),
+                Value::Null => Ok(Value::Null),
+                other => Err(anyhow!("LOWER requires a text argument, found {:?}", other)),
+            },
+            "LENGTH" => match self.evaluate_scalar(Self::arg(&args, 0)?, row_values)? {
+                Value::Text(s) => Ok(Value::Integer(s.chars().count() as i64)),
+                Value::Null => Ok(Value::Null),
+                other => Err(anyhow!("LENGTH requires a text argument, found {:?}", other)),
+            },
+            "ABS" => match self.evaluate_scalar(Self::arg(&args, 0)?, row_values)? {
+                Value::Integer(n) => Ok(Value::Integer(n.abs())),
+                Value::Float(f) => Ok(Value::Float(f.abs())),
+                Value::Null => Ok(Value::Null),
+                other => Err(anyhow!("ABS requires a numeric argument, found {:?}", other)),
+            },
+            "COALESCE" => {
+                for arg in &args {
+                    let value = self.evaluate_scalar(arg, row_values)?;
+                    if !matches!(value, Value::Null) {
+                        return Ok(value);
+                    }
+                }
+                Ok(Value::Null)
+            }
+            other => Err(anyhow!("Unsupported function: {}", other)),
+        }
+    }
+
+    fn arg<'a>(args: &'a [&'a Expr], index: usize) -> Result<&'a Expr> {
+        args.get(index)
+            .copied()
+            .ok_or_else(|| anyhow!("Missing function argument at position {}", index))
+    }
+
+    fn function_arg_exprs(function: &Function) -> Result<Vec<&Expr>> {
+        function
+            .args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                _ => Err(anyhow!("Unsupported function argument: {:?}", arg)),
+            })
+            .collect()
+    }
+
     fn extract_value(&self, expr: &Expr, row_values: &[Value]) -> Result<Value> {
         match expr {
             Expr::Identifier(ident) => {
@@ -95,10 +466,138 @@ impl ExpressionEvaluator {
                 Ok(row_values[idx].clone())
             }
             Expr::Value(sql_val) => self.convert_sql_value(sql_val),
+            Expr::CompoundIdentifier(idents) => self.extract_compound_identifier(idents, row_values),
+            Expr::BinaryOp { left, op, right }
+                if matches!(op, BinaryOperator::Arrow | BinaryOperator::LongArrow) =>
+            {
+                self.extract_json_path(left, op, right, row_values)
+            }
+            Expr::Nested(inner) => self.extract_value(inner, row_values),
             _ => Err(anyhow!("Cannot extract value from expression: {:?}", expr)),
         }
     }
 
+    /// Resolves `column.path.segments` (parsed by sqlparser as a compound
+    /// identifier) against a JSON-typed column, walking the path into the stored
+    /// document. A missing key/index, or navigating a non-JSON column, follows the
+    /// same rules as `extract_json_path` below.
+    fn extract_compound_identifier(&self, idents: &[Ident], row_values: &[Value]) -> Result<Value> {
+        let (head, path) = idents
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty compound identifier"))?;
+
+        let col_name = head.value.as_str();
+        let idx = self
+            .column_names
+            .iter()
+            .position(|name| name == col_name)
+            .ok_or_else(|| anyhow!("Column {} not found", col_name))?;
+
+        match &row_values[idx] {
+            Value::Json(json) => {
+                let segments: Vec<String> = path.iter().map(|i| i.value.clone()).collect();
+                Ok(Self::json_path_value(json, &segments))
+            }
+            Value::Null => Ok(Value::Null),
+            other => Err(anyhow!(
+                "Cannot navigate into non-JSON column {}: {:?}",
+                col_name,
+                other
+            )),
+        }
+    }
+
+    /// Evaluates `left -> right` / `left ->> right`: `left` must resolve to a JSON
+    /// value (or NULL, which short-circuits to NULL), `right` is the object key or
+    /// array index to step into, and `->>` additionally stringifies the result.
+    fn extract_json_path(
+        &self,
+        left: &Expr,
+        op: &BinaryOperator,
+        right: &Expr,
+        row_values: &[Value],
+    ) -> Result<Value> {
+        let left_val = self.extract_value(left, row_values)?;
+        let json = match &left_val {
+            Value::Json(json) => json,
+            Value::Null => return Ok(Value::Null),
+            other => return Err(anyhow!("-> requires a JSON value, found {:?}", other)),
+        };
+
+        let segment = match self.extract_value(right, row_values)? {
+            Value::Text(s) => s,
+            Value::Integer(n) => n.to_string(),
+            other => return Err(anyhow!("Unsupported JSON path segment: {:?}", other)),
+        };
+
+        let result = Self::json_path_value(json, std::slice::from_ref(&segment));
+
+        if matches!(op, BinaryOperator::LongArrow) {
+            Ok(Self::stringify_json_leaf(result))
+        } else {
+            Ok(result)
+        }
+    }
+
+    /// Walks `path` into `json`, resolving object keys and array indices, and
+    /// converts the terminal leaf into a scalar `Value`. A missing key, an
+    /// out-of-bounds index, or stepping into a scalar yields `Value::Null` rather
+    /// than an error, so a bad path composes with the tri-state WHERE logic.
+    fn json_path_value(json: &serde_json::Value, path: &[String]) -> Value {
+        let mut current = json;
+        for segment in path {
+            current = match current {
+                serde_json::Value::Object(map) => match map.get(segment) {
+                    Some(next) => next,
+                    None => return Value::Null,
+                },
+                serde_json::Value::Array(items) => {
+                    match segment.parse::<usize>().ok().and_then(|i| items.get(i)) {
+                        Some(next) => next,
+                        None => return Value::Null,
+                    }
+                }
+                _ => return Value::Null,
+            };
+        }
+        Self::json_leaf_to_value(current)
+    }
+
+    /// Converts a terminal `serde_json::Value` into our `Value`: numbers, strings,
+    /// booleans and null map to the matching scalar, and a nested object/array
+    /// remains `Value::Json` so further navigation is still possible.
+    fn json_leaf_to_value(json: &serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Boolean(*b),
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Value::Integer(i)
+                } else if let Some(f) = n.as_f64() {
+                    Value::Float(f)
+                } else {
+                    Value::Null
+                }
+            }
+            serde_json::Value::String(s) => Value::Text(s.clone()),
+            other => Value::Json(other.clone()),
+        }
+    }
+
+    /// `->>`'s text-coercion step: every leaf shape renders to its textual form,
+    /// matching Postgres's `->>` semantics.
+    fn stringify_json_leaf(value: Value) -> Value {
+        match value {
+            Value::Null => Value::Null,
+            Value::Text(s) => Value::Text(s),
+            Value::Integer(n) => Value::Text(n.to_string()),
+            Value::Float(f) => Value::Text(f.to_string()),
+            Value::Boolean(b) => Value::Text(b.to_string()),
+            Value::Json(j) => Value::Text(j.to_string()),
+            Value::Placeholder(idx) => Value::Placeholder(idx),
+        }
+    }
+
     fn convert_sql_value(&self, sql_val: &SqlValue) -> Result<Value> {
         match sql_val {
             SqlValue::Number(n, _) => {
@@ -117,6 +616,8 @@ impl ExpressionEvaluator {
         }
     }
 
+    /// Compares two non-NULL values. Callers are expected to have already handled
+    /// the NULL case (which yields UNKNOWN, not a comparison result).
     fn compare_values(&self, left: &Value, op: &BinaryOperator, right: &Value) -> Result<bool> {
         match (left, right) {
             (Value::Integer(l), Value::Integer(r)) => Ok(match op {
@@ -151,10 +652,10 @@ impl ExpressionEvaluator {
                 BinaryOperator::NotEq => l != r,
                 _ => return Err(anyhow!("Invalid operator for booleans")),
             }),
-            (Value::Null, Value::Null) => Ok(match op {
-                BinaryOperator::Eq => true,
-                BinaryOperator::NotEq => false,
-                _ => return Err(anyhow!("Invalid operator for nulls")),
+            (Value::Json(l), Value::Json(r)) => Ok(match op {
+                BinaryOperator::Eq => l == r,
+                BinaryOperator::NotEq => l != r,
+                _ => return Err(anyhow!("JSON values only support = and <>")),
             }),
             _ => Err(anyhow!(
                 "Type mismatch in comparison: {:?} vs {:?}",
@@ -169,44 +670,164 @@ impl ExpressionEvaluator {
         negated: bool,
         expr: &Expr,
         pattern: &Expr,
-        _escape_char: Option<&char>,
+        escape_char: Option<&char>,
+        case_insensitive: bool,
         row_values: &[Value],
-    ) -> Result<bool> {
+    ) -> Result<Option<bool>> {
         let value = self.extract_value(expr, row_values)?;
         let pattern_val = self.extract_value(pattern, row_values)?;
 
+        if matches!(value, Value::Null) || matches!(pattern_val, Value::Null) {
+            return Ok(None);
+        }
         if let (Value::Text(text), Value::Text(pat)) = (value, pattern_val) {
-            let regex_pattern = pat.replace('%', ".*").replace('_', ".");
-
-            let regex = Regex::new(&format!("^{}$", regex_pattern))
-                .map_err(|e| anyhow!("Invalid LIKE pattern: {}", e))?;
-
-            let matches = regex.is_match(&text);
-            Ok(if negated { !matches } else { matches })
+            let matches = Self::like_matches(&text, &pat, escape_char.copied(), case_insensitive)?;
+            Ok(Some(if negated { !matches } else { matches }))
         } else {
             Err(anyhow!("LIKE operator requires text values"))
         }
     }
 
+    /// Tokenizes `pattern` into literal runs and wildcards, honoring `escape_char`
+    /// (an escaped `%`/`_`, or the escape character itself, is treated as a literal
+    /// character rather than a wildcard).
+    fn tokenize_like_pattern(pattern: &str, escape_char: Option<char>) -> Vec<LikeToken> {
+        let mut tokens: Vec<LikeToken> = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            if escape_char == Some(c) {
+                if let Some(escaped) = chars.next() {
+                    literal.push(escaped);
+                }
+                continue;
+            }
+
+            match c {
+                '%' => {
+                    if !literal.is_empty() {
+                        tokens.push(LikeToken::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(LikeToken::Many);
+                }
+                '_' => {
+                    if !literal.is_empty() {
+                        tokens.push(LikeToken::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(LikeToken::Any);
+                }
+                other => literal.push(other),
+            }
+        }
+
+        if !literal.is_empty() {
+            tokens.push(LikeToken::Literal(literal));
+        }
+        tokens
+    }
+
+    /// Matches `text` against a SQL `LIKE`/`ILIKE` pattern. Patterns with no
+    /// wildcards (or only a single leading/trailing `%`) are matched directly
+    /// without building a regex; anything more complex falls back to one compiled
+    /// from the escaped literal runs, so regex metacharacters in the pattern never
+    /// leak through unescaped.
+    fn like_matches(
+        text: &str,
+        pattern: &str,
+        escape_char: Option<char>,
+        case_insensitive: bool,
+    ) -> Result<bool> {
+        let tokens = Self::tokenize_like_pattern(pattern, escape_char);
+
+        let eq = |a: &str, b: &str| -> bool {
+            if case_insensitive {
+                a.eq_ignore_ascii_case(b)
+            } else {
+                a == b
+            }
+        };
+        let starts_with = |a: &str, b: &str| -> bool {
+            if case_insensitive {
+                a.to_lowercase().starts_with(&b.to_lowercase())
+            } else {
+                a.starts_with(b)
+            }
+        };
+        let ends_with = |a: &str, b: &str| -> bool {
+            if case_insensitive {
+                a.to_lowercase().ends_with(&b.to_lowercase())
+            } else {
+                a.ends_with(b)
+            }
+        };
+        let contains = |a: &str, b: &str| -> bool {
+            if case_insensitive {
+                a.to_lowercase().contains(&b.to_lowercase())
+            } else {
+                a.contains(b)
+            }
+        };
+
+        match tokens.as_slice() {
+            [] => Ok(text.is_empty()),
+            [LikeToken::Literal(lit)] => Ok(eq(text, lit)),
+            [LikeToken::Many] => Ok(true),
+            [LikeToken::Literal(lit), LikeToken::Many] => Ok(starts_with(text, lit)),
+            [LikeToken::Many, LikeToken::Literal(lit)] => Ok(ends_with(text, lit)),
+            [LikeToken::Many, LikeToken::Literal(lit), LikeToken::Many] => Ok(contains(text, lit)),
+            _ => {
+                let mut regex_pattern = String::from("^");
+                for token in &tokens {
+                    match token {
+                        LikeToken::Literal(lit) => regex_pattern.push_str(&regex::escape(lit)),
+                        LikeToken::Many => regex_pattern.push_str(".*"),
+                        LikeToken::Any => regex_pattern.push('.'),
+                    }
+                }
+                regex_pattern.push('$');
+
+                let regex = regex::RegexBuilder::new(&regex_pattern)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .map_err(|e| anyhow!("Invalid LIKE pattern: {}", e))?;
+                Ok(regex.is_match(text))
+            }
+        }
+    }
+
     fn evaluate_in_list(
         &self,
         expr: &Expr,
         list: &[Expr],
         negated: bool,
         row_values: &[Value],
-    ) -> Result<bool> {
+    ) -> Result<Option<bool>> {
         let value = self.extract_value(expr, row_values)?;
+        if matches!(value, Value::Null) {
+            return Ok(None);
+        }
 
+        let mut saw_null = false;
         let mut found = false;
         for list_expr in list {
             let list_val = self.extract_value(list_expr, row_values)?;
-            if self.values_equal(&value, &list_val) {
+            if matches!(list_val, Value::Null) {
+                saw_null = true;
+            } else if self.values_equal(&value, &list_val) {
                 found = true;
                 break;
             }
         }
 
-        Ok(if negated { !found } else { found })
+        let result = if found {
+            Some(true)
+        } else if saw_null {
+            None
+        } else {
+            Some(false)
+        };
+        Ok(if negated { result.map(|b| !b) } else { result })
     }
 
     fn evaluate_between(
@@ -216,27 +837,44 @@ impl ExpressionEvaluator {
         low: &Expr,
         high: &Expr,
         row_values: &[Value],
-    ) -> Result<bool> {
+    ) -> Result<Option<bool>> {
         let value = self.extract_value(expr, row_values)?;
         let low_val = self.extract_value(low, row_values)?;
         let high_val = self.extract_value(high, row_values)?;
 
-        let gte_low = self.compare_values(&value, &BinaryOperator::GtEq, &low_val)?;
-        let lte_high = self.compare_values(&value, &BinaryOperator::LtEq, &high_val)?;
+        let is_null = |v: &Value| matches!(v, Value::Null);
+        let gte_low = if is_null(&value) || is_null(&low_val) {
+            None
+        } else {
+            Some(self.compare_values(&value, &BinaryOperator::GtEq, &low_val)?)
+        };
+        let lte_high = if is_null(&value) || is_null(&high_val) {
+            None
+        } else {
+            Some(self.compare_values(&value, &BinaryOperator::LtEq, &high_val)?)
+        };
 
-        let in_range = gte_low && lte_high;
-        Ok(if negated { !in_range } else { in_range })
+        let in_range = Self::kleene_and(gte_low, lte_high);
+        Ok(if negated { in_range.map(|b| !b) } else { in_range })
     }
 
     fn values_equal(&self, left: &Value, right: &Value) -> bool {
-        match (left, right) {
-            (Value::Integer(l), Value::Integer(r)) => l == r,
-            (Value::Float(l), Value::Float(r)) => (l - r).abs() < f64::EPSILON,
-            (Value::Text(l), Value::Text(r)) => l == r,
-            (Value::Boolean(l), Value::Boolean(r)) => l == r,
-            (Value::Null, Value::Null) => true,
-            _ => false,
-        }
+        values_equal(left, right)
+    }
+}
+
+/// Equality used for constraint enforcement (uniqueness/foreign-key checks) as
+/// well as WHERE-clause comparisons: exact for most variants, epsilon-tolerant
+/// for floats.
+pub(crate) fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Integer(l), Value::Integer(r)) => l == r,
+        (Value::Float(l), Value::Float(r)) => (l - r).abs() < f64::EPSILON,
+        (Value::Text(l), Value::Text(r)) => l == r,
+        (Value::Boolean(l), Value::Boolean(r)) => l == r,
+        (Value::Json(l), Value::Json(r)) => l == r,
+        (Value::Null, Value::Null) => true,
+        _ => false,
     }
 }
 
@@ -246,6 +884,17 @@ mod tests {
     use sqlparser::dialect::GenericDialect;
     use sqlparser::parser::Parser;
 
+    fn where_expr(sql: &str) -> Expr {
+        let dialect = GenericDialect {};
+        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
+        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
+            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
+                return select.selection.clone().unwrap();
+            }
+        }
+        panic!("Expected a WHERE clause");
+    }
+
     #[test]
     fn test_simple_comparison() {
         let column_names = vec!["id".to_string(), "name".to_string(), "age".to_string()];
@@ -257,18 +906,10 @@ mod tests {
             Value::Integer(30),
         ];
 
-        let sql = "age > 25";
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
-
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
-        }
+        let result = evaluator
+            .evaluate(&where_expr("age > 25"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
     }
 
     #[test]
@@ -278,18 +919,10 @@ mod tests {
 
         let row_values = vec![Value::Integer(1), Value::Integer(30)];
 
-        let sql = "id = 1 AND age > 25";
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
-
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
-        }
+        let result = evaluator
+            .evaluate(&where_expr("id = 1 AND age > 25"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
     }
 
     #[test]
@@ -299,18 +932,10 @@ mod tests {
 
         let row_values = vec![Value::Text("Alice".to_string())];
 
-        let sql = "name = 'Alice'";
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
-
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
-        }
+        let result = evaluator
+            .evaluate(&where_expr("name = 'Alice'"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
     }
 
     #[test]
@@ -320,18 +945,116 @@ mod tests {
 
         let row_values = vec![Value::Text("TestABC".to_string())];
 
-        let sql = "name LIKE 'Test%'";
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
+        let result = evaluator
+            .evaluate(&where_expr("name LIKE 'Test%'"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
+    }
 
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
-        }
+    #[test]
+    fn test_like_escapes_regex_metacharacters() {
+        let evaluator = ExpressionEvaluator::new(vec!["path".to_string()]);
+
+        // A literal '.' in the pattern must not act as a regex wildcard.
+        let matching = vec![Value::Text("a.b".to_string())];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("path LIKE 'a.b'"), &matching)
+                .unwrap(),
+            Some(true)
+        );
+
+        let non_matching = vec![Value::Text("axb".to_string())];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("path LIKE 'a.b'"), &non_matching)
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_like_with_escape_clause_matches_literal_wildcard() {
+        let evaluator = ExpressionEvaluator::new(vec!["code".to_string()]);
+
+        let row_values = vec![Value::Text("50%".to_string())];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr(r"code LIKE '50\%' ESCAPE '\'"), &row_values)
+                .unwrap(),
+            Some(true)
+        );
+
+        let row_values = vec![Value::Text("50x".to_string())];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr(r"code LIKE '50\%' ESCAPE '\'"), &row_values)
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_like_leading_and_trailing_percent() {
+        let evaluator = ExpressionEvaluator::new(vec!["name".to_string()]);
+        let row_values = vec![Value::Text("the quick brown fox".to_string())];
+
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name LIKE '%quick%'"), &row_values)
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name LIKE '%slow%'"), &row_values)
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_ilike_case_insensitive() {
+        let evaluator = ExpressionEvaluator::new(vec!["name".to_string()]);
+        let row_values = vec![Value::Text("TestABC".to_string())];
+
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name ILIKE 'test%'"), &row_values)
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name LIKE 'test%'"), &row_values)
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_ilike_multibyte_does_not_panic() {
+        let evaluator = ExpressionEvaluator::new(vec!["name".to_string()]);
+        let row_values = vec![Value::Text("a€bc".to_string())];
+
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name ILIKE 'aa%'"), &row_values)
+                .unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name ILIKE 'A€%'"), &row_values)
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("name ILIKE '%BC'"), &row_values)
+                .unwrap(),
+            Some(true)
+        );
     }
 
     #[test]
@@ -341,18 +1064,10 @@ mod tests {
 
         let row_values = vec![Value::Text("active".to_string())];
 
-        let sql = "status IN ('active', 'pending')";
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
-
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
-        }
+        let result = evaluator
+            .evaluate(&where_expr("status IN ('active', 'pending')"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
     }
 
     #[test]
@@ -362,17 +1077,345 @@ mod tests {
 
         let row_values = vec![Value::Integer(150)];
 
-        let sql = "price BETWEEN 100 AND 200";
+        let result = evaluator
+            .evaluate(&where_expr("price BETWEEN 100 AND 200"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
+    }
+
+    #[test]
+    fn test_eq_null_is_unknown_not_error() {
+        let column_names = vec!["age".to_string()];
+        let evaluator = ExpressionEvaluator::new(column_names);
+
+        let row_values = vec![Value::Null];
+
+        let result = evaluator
+            .evaluate(&where_expr("age = 25"), &row_values)
+            .unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_is_null_and_is_not_null() {
+        let column_names = vec!["age".to_string()];
+        let evaluator = ExpressionEvaluator::new(column_names);
+
+        let null_row = vec![Value::Null];
+        let present_row = vec![Value::Integer(25)];
+
+        assert_eq!(
+            evaluator.evaluate(&where_expr("age IS NULL"), &null_row).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age IS NOT NULL"), &null_row)
+                .unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age IS NOT NULL"), &present_row)
+                .unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_and_with_null_is_unknown_unless_other_side_false() {
+        let column_names = vec!["a".to_string(), "b".to_string()];
+        let evaluator = ExpressionEvaluator::new(column_names);
+
+        // NULL AND TRUE -> UNKNOWN
+        let row = vec![Value::Null, Value::Integer(1)];
+        let result = evaluator
+            .evaluate(&where_expr("a = 1 AND b = 1"), &row)
+            .unwrap();
+        assert_eq!(result, None);
+
+        // NULL AND FALSE -> FALSE
+        let row = vec![Value::Null, Value::Integer(2)];
+        let result = evaluator
+            .evaluate(&where_expr("a = 1 AND b = 1"), &row)
+            .unwrap();
+        assert_eq!(result, Some(false));
+    }
+
+    #[test]
+    fn test_evaluate_scalar_arithmetic_with_coercion() {
+        let column_names = vec!["price".to_string(), "quantity".to_string()];
+        let evaluator = ExpressionEvaluator::new(column_names);
+        let row_values = vec![Value::Float(2.5), Value::Integer(4)];
+
         let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, &format!("SELECT * FROM t WHERE {}", sql)).unwrap();
+        let ast = Parser::parse_sql(&dialect, "SELECT price * quantity FROM t").unwrap();
+        let expr = match &ast[0] {
+            sqlparser::ast::Statement::Query(query) => match &*query.body {
+                sqlparser::ast::SetExpr::Select(select) => match &select.projection[0] {
+                    sqlparser::ast::SelectItem::UnnamedExpr(expr) => expr.clone(),
+                    _ => panic!("Expected an unnamed expression"),
+                },
+                _ => panic!("Expected a SELECT"),
+            },
+            _ => panic!("Expected a query"),
+        };
 
-        if let sqlparser::ast::Statement::Query(query) = &ast[0] {
-            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
-                if let Some(where_expr) = &select.selection {
-                    let result = evaluator.evaluate(where_expr, &row_values).unwrap();
-                    assert!(result);
-                }
-            }
+        let result = evaluator.evaluate_scalar(&expr, &row_values).unwrap();
+        assert_eq!(result, Value::Float(10.0));
+    }
+
+    #[test]
+    fn test_evaluate_scalar_division_by_zero_errors() {
+        let evaluator = ExpressionEvaluator::new(vec!["n".to_string()]);
+        let row_values = vec![Value::Integer(10)];
+
+        let expr = Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(sqlparser::ast::Ident::new("n"))),
+            op: BinaryOperator::Divide,
+            right: Box::new(Expr::Value(SqlValue::Number("0".to_string(), false))),
+        };
+
+        assert!(evaluator.evaluate_scalar(&expr, &row_values).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_scalar_functions() {
+        let evaluator = ExpressionEvaluator::new(vec!["name".to_string()]);
+        let row_values = vec![Value::Text("Alice".to_string())];
+
+        let upper = Expr::Function(Function {
+            name: sqlparser::ast::ObjectName(vec![sqlparser::ast::Ident::new("UPPER")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                Expr::Identifier(sqlparser::ast::Ident::new("name")),
+            ))],
+            over: None,
+            distinct: false,
+            special: false,
+            order_by: vec![],
+        });
+
+        assert_eq!(
+            evaluator.evaluate_scalar(&upper, &row_values).unwrap(),
+            Value::Text("ALICE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_json_dotted_path_navigation() {
+        let evaluator = ExpressionEvaluator::new(vec!["properties".to_string()]);
+        let row_values = vec![Value::Json(serde_json::json!({"employees": 42, "hq": {"city": "NYC"}}))];
+
+        let result = evaluator
+            .evaluate(&where_expr("properties.employees > 20"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
+
+        let result = evaluator
+            .evaluate(&where_expr("properties.hq.city = 'NYC'"), &row_values)
+            .unwrap();
+        assert_eq!(result, Some(true));
+    }
+
+    #[test]
+    fn test_json_missing_path_is_null_not_error() {
+        let evaluator = ExpressionEvaluator::new(vec!["properties".to_string()]);
+        let row_values = vec![Value::Json(serde_json::json!({"employees": 42}))];
+
+        let result = evaluator
+            .evaluate(&where_expr("properties.missing = 1"), &row_values)
+            .unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_json_arrow_operators() {
+        let evaluator = ExpressionEvaluator::new(vec!["properties".to_string()]);
+        let row_values = vec![Value::Json(serde_json::json!({"employees": 42}))];
+
+        let arrow = Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("properties"))),
+            op: BinaryOperator::Arrow,
+            right: Box::new(Expr::Value(SqlValue::SingleQuotedString("employees".to_string()))),
+        };
+        assert_eq!(
+            evaluator.evaluate_scalar(&arrow, &row_values).unwrap(),
+            Value::Integer(42)
+        );
+
+        let long_arrow = Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("properties"))),
+            op: BinaryOperator::LongArrow,
+            right: Box::new(Expr::Value(SqlValue::SingleQuotedString("employees".to_string()))),
+        };
+        assert_eq!(
+            evaluator.evaluate_scalar(&long_arrow, &row_values).unwrap(),
+            Value::Text("42".to_string())
+        );
+    }
+
+    /// A `SubqueryRunner` stub that ignores correlation and returns a fixed set of
+    /// values, for exercising `IN`/`EXISTS`/`ANY`/`ALL` without a real executor.
+    struct MockRunner {
+        values: Vec<Value>,
+    }
+
+    impl SubqueryRunner for MockRunner {
+        fn run_subquery(
+            &self,
+            _query: &Query,
+            _outer_columns: &[String],
+            _outer_row: &[Value],
+        ) -> Result<Vec<Value>> {
+            Ok(self.values.clone())
+        }
+
+        fn subquery_has_rows(
+            &self,
+            _query: &Query,
+            _outer_columns: &[String],
+            _outer_row: &[Value],
+        ) -> Result<bool> {
+            Ok(!self.values.is_empty())
         }
     }
+
+    #[test]
+    fn test_in_subquery() {
+        let runner = MockRunner {
+            values: vec![Value::Integer(1), Value::Integer(2)],
+        };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["id".to_string()], &runner);
+
+        let matching = vec![Value::Integer(2)];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("id IN (SELECT user_id FROM t2)"), &matching)
+                .unwrap(),
+            Some(true)
+        );
+
+        let non_matching = vec![Value::Integer(3)];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("id IN (SELECT user_id FROM t2)"), &non_matching)
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_in_subquery_with_null_candidate_is_unknown_when_unmatched() {
+        let runner = MockRunner {
+            values: vec![Value::Integer(1), Value::Null],
+        };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["id".to_string()], &runner);
+
+        let row = vec![Value::Integer(3)];
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("id IN (SELECT user_id FROM t2)"), &row)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_exists_subquery() {
+        let with_rows = MockRunner {
+            values: vec![Value::Integer(1)],
+        };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["id".to_string()], &with_rows);
+        assert_eq!(
+            evaluator
+                .evaluate(
+                    &where_expr("EXISTS (SELECT 1 FROM t2)"),
+                    &[Value::Integer(1)],
+                )
+                .unwrap(),
+            Some(true)
+        );
+
+        let empty = MockRunner { values: vec![] };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["id".to_string()], &empty);
+        assert_eq!(
+            evaluator
+                .evaluate(
+                    &where_expr("NOT EXISTS (SELECT 1 FROM t2)"),
+                    &[Value::Integer(1)],
+                )
+                .unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_any_all_subquery() {
+        let runner = MockRunner {
+            values: vec![Value::Integer(10), Value::Integer(20)],
+        };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["age".to_string()], &runner);
+
+        // 15 > ANY(10, 20) -> true (holds against 10)
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age > ANY (SELECT n FROM t2)"), &[Value::Integer(15)])
+                .unwrap(),
+            Some(true)
+        );
+
+        // 25 > ALL(10, 20) -> true
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age > ALL (SELECT n FROM t2)"), &[Value::Integer(25)])
+                .unwrap(),
+            Some(true)
+        );
+
+        // 15 > ALL(10, 20) -> false (fails against 20)
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age > ALL (SELECT n FROM t2)"), &[Value::Integer(15)])
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_all_over_empty_subquery_is_vacuously_true() {
+        let empty = MockRunner { values: vec![] };
+        let evaluator = ExpressionEvaluator::with_runner(vec!["age".to_string()], &empty);
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age > ALL (SELECT n FROM t2)"), &[Value::Integer(15)])
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            evaluator
+                .evaluate(&where_expr("age > ANY (SELECT n FROM t2)"), &[Value::Integer(15)])
+                .unwrap(),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_or_with_null_is_true_if_other_side_true() {
+        let column_names = vec!["a".to_string(), "b".to_string()];
+        let evaluator = ExpressionEvaluator::new(column_names);
+
+        // NULL OR TRUE -> TRUE
+        let row = vec![Value::Null, Value::Integer(1)];
+        let result = evaluator
+            .evaluate(&where_expr("a = 1 OR b = 1"), &row)
+            .unwrap();
+        assert_eq!(result, Some(true));
+
+        // NULL OR FALSE -> UNKNOWN
+        let row = vec![Value::Null, Value::Integer(2)];
+        let result = evaluator
+            .evaluate(&where_expr("a = 1 OR b = 1"), &row)
+            .unwrap();
+        assert_eq!(result, None);
+    }
 }
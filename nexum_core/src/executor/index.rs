@@ -0,0 +1,157 @@
+//! Order-preserving byte encoding for secondary index keys, plus the key-layout
+//! helpers used to keep an index's sled-backed keyspace in sync with table rows.
+//!
+//! Index entries live under `idx:{table}:{column}:{encoded_value}{row_key}`, so a
+//! point lookup is a prefix scan and a range scan is a byte-range scan over the same
+//! keyspace; both rely on `encode_value` preserving the `Value` ordering.
+
+use crate::sql::types::Value;
+
+const PREFIX: &[u8] = b"idx:";
+
+/// Encodes a `Value` into bytes whose lexicographic order matches the value's own
+/// ordering, so the encoded form can be used directly as a sled scan key.
+///
+/// Returns `None` for `Value::Null`, since NULLs are excluded from indexes.
+pub fn encode_value(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Integer(n) => {
+            // Flip the sign bit so negative integers sort before positive ones
+            // under unsigned big-endian byte comparison.
+            let flipped = (*n as u64) ^ 0x8000_0000_0000_0000;
+            Some(flipped.to_be_bytes().to_vec())
+        }
+        Value::Float(f) => {
+            let bits = f.to_bits();
+            let flipped = if *f >= 0.0 {
+                bits ^ 0x8000_0000_0000_0000
+            } else {
+                !bits
+            };
+            Some(flipped.to_be_bytes().to_vec())
+        }
+        // A 0x00 terminator keeps one string's encoding from being a prefix of a
+        // longer one's, so an equality or range scan on "b" does not take in "ba".
+        Value::Text(s) => {
+            let mut bytes = s.as_bytes().to_vec();
+            bytes.push(0);
+            Some(bytes)
+        }
+        Value::Boolean(b) => Some(vec![*b as u8]),
+        // JSON documents have no natural total order to index on; callers fall
+        // back to a full scan for predicates over a JSON column, same as for NULL.
+        Value::Json(_) => None,
+        Value::Null => None,
+        // Bound before execution reaches indexing; treated like NULL if it ever
+        // slips through unbound.
+        Value::Placeholder(_) => None,
+    }
+}
+
+/// `idx:{table}:{column}:` — the prefix shared by every entry of this index.
+pub fn index_prefix(table: &str, column: &str) -> Vec<u8> {
+    let mut key = PREFIX.to_vec();
+    key.extend_from_slice(table.as_bytes());
+    key.push(b':');
+    key.extend_from_slice(column.as_bytes());
+    key.push(b':');
+    key
+}
+
+/// The full index entry key for one `(value, row_key)` pair.
+pub fn entry_key(table: &str, column: &str, value: &Value, row_key: &[u8]) -> Option<Vec<u8>> {
+    let mut key = index_prefix(table, column);
+    key.extend_from_slice(&encode_value(value)?);
+    key.extend_from_slice(row_key);
+    Some(key)
+}
+
+/// Byte range covering entries whose encoded value equals `value` exactly.
+pub fn eq_range(table: &str, column: &str, value: &Value) -> Option<(Vec<u8>, Vec<u8>)> {
+    let encoded = encode_value(value)?;
+    let mut start = index_prefix(table, column);
+    start.extend_from_slice(&encoded);
+    let mut end = start.clone();
+    end.push(0xFF);
+    Some((start, end))
+}
+
+/// Byte range covering entries whose encoded value falls within `(lower, upper)`,
+/// where either bound may be absent to mean "unbounded".
+pub fn bounds_range(
+    table: &str,
+    column: &str,
+    lower: Option<&Value>,
+    upper: Option<&Value>,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    let prefix = index_prefix(table, column);
+
+    let start = match lower {
+        Some(v) => {
+            let mut key = prefix.clone();
+            key.extend_from_slice(&encode_value(v)?);
+            key
+        }
+        None => prefix.clone(),
+    };
+
+    let end = match upper {
+        Some(v) => {
+            let mut key = prefix.clone();
+            key.extend_from_slice(&encode_value(v)?);
+            key.push(0xFF);
+            key
+        }
+        None => {
+            let mut key = prefix.clone();
+            key.push(0xFF);
+            key
+        }
+    };
+
+    Some((start, end))
+}
+
+/// Row keys generated by `Executor::generate_row_key` are a fixed-width 16-byte
+/// (u128) big-endian timestamp, so the suffix is always the entry's last 16 bytes.
+const ROW_KEY_LEN: usize = 16;
+
+/// Extracts the row key suffix from a full index entry key.
+pub fn row_key_from_entry(entry: &[u8]) -> Vec<u8> {
+    entry[entry.len() - ROW_KEY_LEN..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_encoding_preserves_order() {
+        let mut pairs: Vec<(i64, Vec<u8>)> = vec![-5, 10, 0, -100, 42]
+            .into_iter()
+            .map(|n| (n, encode_value(&Value::Integer(n)).unwrap()))
+            .collect();
+        pairs.sort_by(|a, b| a.1.cmp(&b.1));
+        let ordered: Vec<i64> = pairs.into_iter().map(|(n, _)| n).collect();
+        assert_eq!(ordered, vec![-100, -5, 0, 10, 42]);
+    }
+
+    #[test]
+    fn test_text_encoding_preserves_order() {
+        let a = encode_value(&Value::Text("apple".to_string())).unwrap();
+        let b = encode_value(&Value::Text("banana".to_string())).unwrap();
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_null_has_no_encoding() {
+        assert_eq!(encode_value(&Value::Null), None);
+    }
+
+    #[test]
+    fn test_entry_key_roundtrip_row_key() {
+        let row_key = 123456u128.to_be_bytes().to_vec();
+        let key = entry_key("users", "id", &Value::Integer(7), &row_key).unwrap();
+        assert_eq!(row_key_from_entry(&key), row_key);
+    }
+}
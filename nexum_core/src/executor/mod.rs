@@ -1,22 +1,90 @@
+use anyhow::anyhow;
 use crate::bridge::SemanticCache;
 use crate::catalog::Catalog;
-use crate::sql::types::{Statement, Value};
+use crate::sql::binder::Binder;
+use crate::sql::planner::{AccessPath, ExplainRow, FkCheck, IndexBounds, Planner};
+use crate::sql::types::{
+    AggregateFunc, AlterTableOperation, Column, CompareOp, DataType, OnConflict, Predicate,
+    SelectItem, Statement, TableSchema, Value,
+};
 use crate::storage::{Result, StorageEngine, StorageError};
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
+pub mod bitmap;
 pub mod filter;
+pub mod index;
 use filter::ExpressionEvaluator;
 
+/// Materializes a subquery's result set for `IN (SELECT ...)`, `EXISTS (...)`, and
+/// `= ANY/ALL (...)` predicates, correlating it against the outer query's current
+/// row. `ExpressionEvaluator` holds one of these as a trait object rather than a
+/// concrete `Executor` so `executor::filter` doesn't need to depend back on this
+/// module's storage/catalog wiring.
+pub trait SubqueryRunner {
+    /// Runs `query`, correlated against `outer_row`, and returns the first
+    /// projected column's value for each matching row. Used by `IN`/`= ANY/ALL`.
+    fn run_subquery(
+        &self,
+        query: &sqlparser::ast::Query,
+        outer_columns: &[String],
+        outer_row: &[Value],
+    ) -> anyhow::Result<Vec<Value>>;
+
+    /// Runs `query`, correlated against `outer_row`, and reports whether it
+    /// produced at least one row. Used by `EXISTS`.
+    fn subquery_has_rows(
+        &self,
+        query: &sqlparser::ast::Query,
+        outer_columns: &[String],
+        outer_row: &[Value],
+    ) -> anyhow::Result<bool>;
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Row {
     pub values: Vec<Value>,
 }
 
+/// The kind of mutation a [`ChangeEvent`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOp {
+    Insert,
+    Delete,
+    Update,
+}
+
+/// One row-level mutation observed by a watcher registered via
+/// `Executor::watch`. `row` is the row's state after the mutation (the
+/// deleted row, for `Delete`); `old_row` is its state before, set only for
+/// `Update`.
+#[derive(Debug, Clone)]
+pub struct ChangeEvent {
+    pub table: String,
+    pub op: ChangeOp,
+    pub row: Row,
+    pub old_row: Option<Row>,
+}
+
+/// A registered observer: `table` narrows delivery to one table's events,
+/// `None` subscribes to every table.
+struct Watcher {
+    table: Option<String>,
+    sender: std::sync::mpsc::Sender<ChangeEvent>,
+}
+
 pub struct Executor {
     storage: StorageEngine,
     catalog: Catalog,
     cache: Option<SemanticCache>,
+    watchers: std::sync::Mutex<Vec<Watcher>>,
+    /// `Some(buffer)` while a `BEGIN`/`ROLLBACK`/`COMMIT` block (or
+    /// `Executor::transaction`) is in progress: events from statements in that
+    /// block accumulate here instead of reaching watchers immediately, so a
+    /// `ROLLBACK` can discard them unobserved. `None` outside a transaction,
+    /// where each statement dispatches its own events as soon as it succeeds.
+    pending_events: std::sync::Mutex<Option<Vec<ChangeEvent>>>,
 }
 
 impl Executor {
@@ -26,7 +94,56 @@ impl Executor {
             storage,
             catalog,
             cache: None,
+            watchers: std::sync::Mutex::new(Vec::new()),
+            pending_events: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Registers a new watcher and returns the receiving end of its channel;
+    /// `table` narrows delivery to that table's events, `None` subscribes to
+    /// every table. Events are delivered as a batch after each successful
+    /// `execute`/transaction commit; a rolled-back transaction's events are
+    /// never sent.
+    pub fn watch(&self, table: Option<String>) -> std::sync::mpsc::Receiver<ChangeEvent> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.watchers.lock().unwrap().push(Watcher { table, sender });
+        receiver
+    }
+
+    /// Routes `events` to every watcher whose table filter matches, either
+    /// right away (`record_events`'s caller, outside a transaction) or all at
+    /// once when a transaction commits. Watchers whose receiver was dropped
+    /// are pruned rather than kept around failing silently forever.
+    fn dispatch_events(&self, events: &[ChangeEvent]) {
+        if events.is_empty() {
+            return;
+        }
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers.retain(|watcher| {
+            for event in events {
+                if let Some(only_table) = &watcher.table {
+                    if only_table != &event.table {
+                        continue;
+                    }
+                }
+                if watcher.sender.send(event.clone()).is_err() {
+                    return false;
+                }
+            }
+            true
+        });
+    }
+
+    /// Called once a statement has fully succeeded: buffers `events` if a
+    /// transaction is open, or dispatches them immediately otherwise.
+    fn record_events(&self, events: Vec<ChangeEvent>) {
+        let mut pending = self.pending_events.lock().unwrap();
+        if let Some(buffer) = pending.as_mut() {
+            buffer.extend(events);
+            return;
         }
+        drop(pending);
+        self.dispatch_events(&events);
     }
 
     pub fn with_cache(self) -> Self {
@@ -52,40 +169,170 @@ impl Executor {
 
         let result =
             match statement {
-                Statement::CreateTable { name, columns } => {
-                    self.catalog.create_table(&name, columns)?;
+                Statement::CreateTable {
+                    name,
+                    columns,
+                    unique_groups,
+                    foreign_keys,
+                } => {
+                    self.catalog
+                        .create_table(&name, columns, foreign_keys, unique_groups)?;
                     Ok(ExecutionResult::Created { table: name })
                 }
                 Statement::Insert {
                     table,
-                    columns: _,
+                    columns,
                     values,
+                    returning,
+                    on_conflict,
                 } => {
-                    let _schema = self.catalog.get_table(&table)?.ok_or_else(|| {
+                    let schema = self.catalog.get_table(&table)?.ok_or_else(|| {
                         StorageError::ReadError(format!("Table {} not found", table))
                     })?;
+                    let indexed_columns = self.catalog.indexed_columns(&table)?;
+                    let bitmap_columns = self.catalog.bitmap_indexed_columns(&table)?;
+                    let data_prefix = Self::table_data_prefix(&table);
+                    let mut inserted_rows = Vec::new();
+                    let mut change_events = Vec::new();
 
                     for row_values in &values {
+                        let row_values = Self::resolve_insert_row(&schema, &columns, row_values)?;
+                        Self::check_not_null(&schema, &row_values)?;
+
+                        // `ON CONFLICT REPLACE` looks for a row already occupying
+                        // one of this row's unique slots *before* the usual
+                        // uniqueness check, so a hit there is treated as the row
+                        // to overwrite rather than a violation to reject.
+                        let conflict = if on_conflict == OnConflict::Replace {
+                            self.find_unique_conflict(&table, &schema, &row_values)?
+                        } else {
+                            None
+                        };
+
+                        self.check_unique_constraints(
+                            &table,
+                            &schema,
+                            &row_values,
+                            conflict.as_ref().map(|(key, _)| key.as_slice()),
+                        )?;
+                        self.check_foreign_keys(&schema, &row_values)?;
+
                         let row = Row {
                             values: row_values.clone(),
                         };
-                        let key = self.generate_row_key(&table);
                         let value = serde_json::to_vec(&row)?;
+
+                        let (key, old_row) = match conflict {
+                            Some((key, old_row)) => (key, Some(old_row)),
+                            None => (self.generate_row_key(&table), None),
+                        };
                         self.storage.set(&key, &value)?;
+
+                        let row_key_suffix = &key[data_prefix.len()..];
+
+                        if let Some(old_row) = &old_row {
+                            self.update_indexes_for_row(
+                                &table,
+                                &schema,
+                                &indexed_columns,
+                                &old_row.values,
+                                row_key_suffix,
+                                false,
+                            )?;
+                        }
+                        self.update_indexes_for_row(
+                            &table,
+                            &schema,
+                            &indexed_columns,
+                            &row_values,
+                            row_key_suffix,
+                            true,
+                        )?;
+
+                        match &old_row {
+                            Some(old_row) => {
+                                if let Some(row_id) =
+                                    bitmap::row_id_for_key(&self.storage, &table, row_key_suffix)?
+                                {
+                                    for column in &bitmap_columns {
+                                        if let Some(col_idx) =
+                                            schema.columns.iter().position(|c| &c.name == column)
+                                        {
+                                            bitmap::remove_row(
+                                                &self.storage,
+                                                &table,
+                                                column,
+                                                &old_row.values[col_idx],
+                                                row_id,
+                                            )?;
+                                            bitmap::add_row(
+                                                &self.storage,
+                                                &table,
+                                                column,
+                                                &row_values[col_idx],
+                                                row_id,
+                                            )?;
+                                        }
+                                    }
+                                }
+                            }
+                            None => {
+                                self.update_bitmap_indexes_for_insert(
+                                    &table,
+                                    &schema,
+                                    &bitmap_columns,
+                                    &row_values,
+                                    row_key_suffix,
+                                )?;
+                            }
+                        }
+
+                        change_events.push(ChangeEvent {
+                            table: table.clone(),
+                            op: if old_row.is_some() {
+                                ChangeOp::Update
+                            } else {
+                                ChangeOp::Insert
+                            },
+                            row: row.clone(),
+                            old_row,
+                        });
+                        if returning.is_some() {
+                            inserted_rows.push(row);
+                        }
                     }
 
-                    Ok(ExecutionResult::Inserted {
-                        table,
-                        rows: values.len(),
-                    })
+                    self.record_events(change_events);
+
+                    match returning {
+                        Some(columns) => {
+                            let rows = Self::project_returning(&schema, &columns, inserted_rows)?;
+                            Ok(ExecutionResult::Selected { columns, rows })
+                        }
+                        None => Ok(ExecutionResult::Inserted {
+                            table,
+                            rows: values.len(),
+                        }),
+                    }
                 }
                 Statement::Select {
                     table,
-                    columns,
+                    joins,
+                    projection,
                     where_clause,
+                    group_by,
+                    having,
                     order_by,
                     limit,
                 } => {
+                    if !joins.is_empty() {
+                        return Err(StorageError::ReadError(
+                            "JOIN execution is not yet supported".to_string(),
+                        ));
+                    }
+
+                    let columns = Self::resolve_projection_labels(&projection);
+
                     if let Some(cache) = &self.cache {
                         let query_str = format!("SELECT {:?} FROM {}", columns, table);
 
@@ -101,63 +348,190 @@ impl Executor {
                         StorageError::ReadError(format!("Table {} not found", table))
                     })?;
 
-                    let prefix = Self::table_data_prefix(&table);
-                    let all_rows = self.storage.scan_prefix(&prefix)?;
+                    // Resolves the projection against the catalog up front, so an
+                    // unknown column is reported even when the WHERE clause matches
+                    // zero rows; `project_rows` only re-checks column names while
+                    // mapping over actual rows and would otherwise miss this case.
+                    Binder::bind_select_columns(&self.catalog, &table, &projection)?;
 
-                    let mut rows: Vec<Row> = all_rows
-                        .iter()
-                        .filter_map(|(_, v)| serde_json::from_slice::<Row>(v).ok())
-                        .collect();
+                    let access_path =
+                        Planner::choose_access_path(&self.catalog, &table, where_clause.as_deref())?;
 
-                    if let Some(where_expr) = where_clause {
-                        let column_names: Vec<String> =
-                            schema.columns.iter().map(|c| c.name.clone()).collect();
-                        let evaluator = ExpressionEvaluator::new(column_names);
+                    let mut rows = match access_path {
+                        AccessPath::FullScan => {
+                            let prefix = Self::table_data_prefix(&table);
+                            let all_rows = self.storage.scan_prefix(&prefix)?;
+                            let mut rows: Vec<Row> = all_rows
+                                .iter()
+                                .filter_map(|(_, v)| serde_json::from_slice::<Row>(v).ok())
+                                .map(|row| Self::pad_row(&schema, row))
+                                .collect();
 
-                        rows.retain(|row| {
-                            evaluator
-                                .evaluate(&where_expr, &row.values)
-                                .unwrap_or(false)
-                        });
+                            if let Some(where_expr) = &where_clause {
+                                let column_names: Vec<String> =
+                                    schema.columns.iter().map(|c| c.name.clone()).collect();
+                                let evaluator =
+                                    ExpressionEvaluator::with_runner(column_names, self);
 
-                        log::debug!("Filtered {} rows using WHERE clause", rows.len());
-                    }
+                                rows.retain(|row| {
+                                    matches!(
+                                        evaluator.evaluate(where_expr, &row.values),
+                                        Ok(Some(true))
+                                    )
+                                });
 
-                    if let Some(order_clauses) = order_by {
-                        let column_names: Vec<String> =
-                            schema.columns.iter().map(|c| c.name.clone()).collect();
+                                log::debug!("Filtered {} rows using WHERE clause", rows.len());
+                            }
+
+                            rows
+                        }
+                        AccessPath::IndexScan {
+                            index_column,
+                            bounds,
+                            residual,
+                        } => {
+                            log::debug!(
+                                "Using index scan on {}.{} instead of a full scan",
+                                table,
+                                index_column
+                            );
+
+                            let data_prefix = Self::table_data_prefix(&table);
+                            let candidates: Vec<Row> = self
+                                .scan_index(&table, &index_column, &bounds, &data_prefix)?
+                                .into_iter()
+                                .map(|row| Self::pad_row(&schema, row))
+                                .collect();
+
+                            let column_names: Vec<String> =
+                                schema.columns.iter().map(|c| c.name.clone()).collect();
+                            let evaluator = ExpressionEvaluator::with_runner(column_names, self);
+
+                            let rows: Vec<Row> = candidates
+                                .into_iter()
+                                .filter(|row| {
+                                    matches!(evaluator.evaluate(&residual, &row.values), Ok(Some(true)))
+                                })
+                                .collect();
+
+                            log::debug!(
+                                "Index scan on {}.{} produced {} rows after residual filter",
+                                table,
+                                index_column,
+                                rows.len()
+                            );
+
+                            rows
+                        }
+                        AccessPath::BitmapScan { predicate } => {
+                            log::debug!("Using bitmap index scan on {}", table);
 
-                        for order_clause in order_clauses.iter().rev() {
-                            if let Some(col_idx) =
-                                column_names.iter().position(|c| c == &order_clause.column)
-                            {
-                                rows.sort_by(|a, b| {
-                                    let ordering = match (&a.values[col_idx], &b.values[col_idx]) {
-                                        (Value::Integer(av), Value::Integer(bv)) => av.cmp(bv),
-                                        (Value::Float(av), Value::Float(bv)) => {
-                                            av.partial_cmp(bv).unwrap_or(std::cmp::Ordering::Equal)
+                            match self.resolve_bitmap(&table, &predicate)? {
+                                Some(row_ids) => {
+                                    let mut rows = Vec::with_capacity(row_ids.len() as usize);
+                                    for row_id in row_ids.iter() {
+                                        let Some(row_key_suffix) =
+                                            bitmap::row_key_for_id(&self.storage, &table, row_id)?
+                                        else {
+                                            continue;
+                                        };
+                                        let mut row_key = Self::table_data_prefix(&table);
+                                        row_key.extend_from_slice(&row_key_suffix);
+                                        if let Some(value) = self.storage.get(&row_key)? {
+                                            if let Ok(row) = serde_json::from_slice::<Row>(&value) {
+                                                rows.push(Self::pad_row(&schema, row));
+                                            }
                                         }
-                                        (Value::Text(av), Value::Text(bv)) => av.cmp(bv),
-                                        (Value::Boolean(av), Value::Boolean(bv)) => av.cmp(bv),
-                                        _ => std::cmp::Ordering::Equal,
-                                    };
+                                    }
+
+                                    log::debug!(
+                                        "Bitmap scan on {} produced {} rows",
+                                        table,
+                                        rows.len()
+                                    );
 
-                                    if order_clause.ascending {
-                                        ordering
-                                    } else {
-                                        ordering.reverse()
+                                    rows
+                                }
+                                None => {
+                                    // A leaf's literal value has no order-preserving
+                                    // encoding (e.g. a `NULL` compared with `=`); fall back
+                                    // to a full scan with the original WHERE re-applied.
+                                    let prefix = Self::table_data_prefix(&table);
+                                    let all_rows = self.storage.scan_prefix(&prefix)?;
+                                    let mut rows: Vec<Row> = all_rows
+                                        .iter()
+                                        .filter_map(|(_, v)| serde_json::from_slice::<Row>(v).ok())
+                                        .map(|row| Self::pad_row(&schema, row))
+                                        .collect();
+
+                                    if let Some(where_expr) = &where_clause {
+                                        let column_names: Vec<String> =
+                                            schema.columns.iter().map(|c| c.name.clone()).collect();
+                                        let evaluator =
+                                            ExpressionEvaluator::with_runner(column_names, self);
+
+                                        rows.retain(|row| {
+                                            matches!(
+                                                evaluator.evaluate(where_expr, &row.values),
+                                                Ok(Some(true))
+                                            )
+                                        });
                                     }
-                                });
+
+                                    rows
+                                }
                             }
                         }
+                    };
 
-                        log::debug!("Sorted {} rows using ORDER BY", rows.len());
-                    }
+                    let rows = if Self::has_aggregates(&projection) || !group_by.is_empty() {
+                        Self::aggregate_rows(
+                            &schema,
+                            &projection,
+                            &group_by,
+                            having.as_deref(),
+                            rows,
+                        )?
+                    } else {
+                        if let Some(order_clauses) = order_by {
+                            let column_names: Vec<String> =
+                                schema.columns.iter().map(|c| c.name.clone()).collect();
 
-                    if let Some(limit_count) = limit {
-                        rows.truncate(limit_count);
-                        log::debug!("Limited to {} rows using LIMIT", limit_count);
-                    }
+                            for order_clause in order_clauses.iter().rev() {
+                                if let Some(col_idx) =
+                                    column_names.iter().position(|c| c == &order_clause.column)
+                                {
+                                    rows.sort_by(|a, b| {
+                                        let ordering = match (&a.values[col_idx], &b.values[col_idx])
+                                        {
+                                            (Value::Integer(av), Value::Integer(bv)) => av.cmp(bv),
+                                            (Value::Float(av), Value::Float(bv)) => {
+                                                av.partial_cmp(bv).unwrap_or(std::cmp::Ordering::Equal)
+                                            }
+                                            (Value::Text(av), Value::Text(bv)) => av.cmp(bv),
+                                            (Value::Boolean(av), Value::Boolean(bv)) => av.cmp(bv),
+                                            _ => std::cmp::Ordering::Equal,
+                                        };
+
+                                        if order_clause.ascending {
+                                            ordering
+                                        } else {
+                                            ordering.reverse()
+                                        }
+                                    });
+                                }
+                            }
+
+                            log::debug!("Sorted {} rows using ORDER BY", rows.len());
+                        }
+
+                        if let Some(limit_count) = limit {
+                            rows.truncate(limit_count);
+                            log::debug!("Limited to {} rows using LIMIT", limit_count);
+                        }
+
+                        Self::project_rows(&projection, &schema, rows)?
+                    };
 
                     if let Some(cache) = &self.cache {
                         let query_str = format!("SELECT {:?} FROM {}", columns, table);
@@ -170,30 +544,152 @@ impl Executor {
                 Statement::Delete {
                     table,
                     where_clause,
+                    returning,
                 } => {
                     let schema = self.catalog.get_table(&table)?.ok_or_else(|| {
                         StorageError::ReadError(format!("Table {} not found", table))
                     })?;
 
+                    // Computed once per statement rather than once per deleted row:
+                    // an empty list means no other table can possibly reference
+                    // `table`, so every row skips `enforce_fk_on_delete` (and the
+                    // catalog lookup it would otherwise repeat) entirely.
+                    let fk_checks: Vec<FkCheck> = self
+                        .catalog
+                        .tables_referencing(&table)?
+                        .into_iter()
+                        .map(|(child_table, fk)| FkCheck {
+                            child_table,
+                            child_column: fk.columns.join(", "),
+                            action: fk.on_delete,
+                        })
+                        .collect();
+
                     let prefix = Self::table_data_prefix(&table);
+                    let indexed_columns = self.catalog.indexed_columns(&table)?;
+                    let bitmap_columns = self.catalog.bitmap_indexed_columns(&table)?;
                     let mut deleted_count = 0;
+                    let mut deleted_rows = Vec::new();
+                    let mut change_events = Vec::new();
 
                     if let Some(where_expr) = where_clause {
+                        let access_path = Planner::choose_access_path(
+                            &self.catalog,
+                            &table,
+                            Some(&where_expr),
+                        )?;
+
+                        let candidate_keys: Option<Vec<Vec<u8>>> = match &access_path {
+                            AccessPath::BitmapScan { predicate } => self
+                                .resolve_bitmap(&table, predicate)?
+                                .map(|row_ids| {
+                                    row_ids
+                                        .iter()
+                                        .filter_map(|row_id| {
+                                            bitmap::row_key_for_id(&self.storage, &table, row_id)
+                                                .ok()
+                                                .flatten()
+                                        })
+                                        .map(|suffix| {
+                                            let mut key = prefix.clone();
+                                            key.extend_from_slice(&suffix);
+                                            key
+                                        })
+                                        .collect()
+                                }),
+                            AccessPath::IndexScan {
+                                index_column,
+                                bounds,
+                                ..
+                            } => {
+                                let range = match bounds {
+                                    IndexBounds::Eq(value) => {
+                                        index::eq_range(&table, index_column, value)
+                                    }
+                                    IndexBounds::Range { lower, upper } => index::bounds_range(
+                                        &table,
+                                        index_column,
+                                        lower.as_ref(),
+                                        upper.as_ref(),
+                                    ),
+                                };
+                                match range {
+                                    Some((start, end)) => Some(
+                                        self.storage
+                                            .scan_range(start..end)?
+                                            .iter()
+                                            .map(|(entry_key, _)| {
+                                                let suffix = index::row_key_from_entry(entry_key);
+                                                let mut key = prefix.clone();
+                                                key.extend_from_slice(&suffix);
+                                                key
+                                            })
+                                            .collect(),
+                                    ),
+                                    None => Some(Vec::new()),
+                                }
+                            }
+                            AccessPath::FullScan => None,
+                        };
+
                         let column_names: Vec<String> =
                             schema.columns.iter().map(|c| c.name.clone()).collect();
-                        let evaluator = ExpressionEvaluator::new(column_names);
+                        let evaluator = ExpressionEvaluator::with_runner(column_names, self);
 
-                        // Process rows incrementally to avoid loading all into memory
-                        let all_rows = self.storage.scan_prefix(&prefix)?;
-                        for (key, value) in &all_rows {
+                        let candidates: Vec<(Vec<u8>, Vec<u8>)> = match candidate_keys {
+                            Some(keys) => keys
+                                .into_iter()
+                                .filter_map(|key| {
+                                    let value = self.storage.get(&key).ok()??;
+                                    Some((key, value))
+                                })
+                                .collect(),
+                            None => self.storage.scan_prefix(&prefix)?,
+                        };
+
+                        for (key, value) in &candidates {
                             if let Ok(row) = serde_json::from_slice::<Row>(value) {
+                                let row = Self::pad_row(&schema, row);
                                 match evaluator.evaluate(&where_expr, &row.values) {
-                                    Ok(true) => {
+                                    Ok(Some(true)) => {
+                                        if !fk_checks.is_empty() {
+                                            self.enforce_fk_on_delete(
+                                                &table,
+                                                &schema,
+                                                &row.values,
+                                                &mut change_events,
+                                            )?;
+                                        }
                                         self.storage.delete(key)?;
+                                        let row_key_suffix = &key[prefix.len()..];
+                                        self.update_indexes_for_row(
+                                            &table,
+                                            &schema,
+                                            &indexed_columns,
+                                            &row.values,
+                                            row_key_suffix,
+                                            false,
+                                        )?;
+                                        self.update_bitmap_indexes_for_delete(
+                                            &table,
+                                            &schema,
+                                            &bitmap_columns,
+                                            &row.values,
+                                            row_key_suffix,
+                                        )?;
                                         deleted_count += 1;
+                                        change_events.push(ChangeEvent {
+                                            table: table.clone(),
+                                            op: ChangeOp::Delete,
+                                            row: row.clone(),
+                                            old_row: None,
+                                        });
+                                        if returning.is_some() {
+                                            deleted_rows.push(row);
+                                        }
                                     }
-                                    Ok(false) => {
-                                        // Row doesn't match WHERE condition, skip
+                                    Ok(Some(false)) | Ok(None) => {
+                                        // Row doesn't match WHERE condition (false or UNKNOWN), skip
                                     }
                                     Err(e) => {
                                         return Err(StorageError::ReadError(format!(
@@ -207,17 +703,439 @@ impl Executor {
                         // No WHERE clause - delete all rows
                         log::warn!("DELETE without WHERE clause will remove all rows from table '{}'", table);
                         let all_rows = self.storage.scan_prefix(&prefix)?;
-                        for (key, _) in &all_rows {
-                            self.storage.delete(key)?;
+                        for (key, value) in &all_rows {
+                            if let Ok(row) = serde_json::from_slice::<Row>(value) {
+                                let row = Self::pad_row(&schema, row);
+                                if !fk_checks.is_empty() {
+                                    self.enforce_fk_on_delete(
+                                        &table,
+                                        &schema,
+                                        &row.values,
+                                        &mut change_events,
+                                    )?;
+                                }
+                                self.storage.delete(key)?;
+                                let row_key_suffix = &key[prefix.len()..];
+                                self.update_indexes_for_row(
+                                    &table,
+                                    &schema,
+                                    &indexed_columns,
+                                    &row.values,
+                                    row_key_suffix,
+                                    false,
+                                )?;
+                                self.update_bitmap_indexes_for_delete(
+                                    &table,
+                                    &schema,
+                                    &bitmap_columns,
+                                    &row.values,
+                                    row_key_suffix,
+                                )?;
+                                change_events.push(ChangeEvent {
+                                    table: table.clone(),
+                                    op: ChangeOp::Delete,
+                                    row: row.clone(),
+                                    old_row: None,
+                                });
+                                if returning.is_some() {
+                                    deleted_rows.push(row);
+                                }
+                            } else {
+                                self.storage.delete(key)?;
+                            }
                             deleted_count += 1;
                         }
                     }
 
-                    Ok(ExecutionResult::Deleted {
+                    self.record_events(change_events);
+
+                    match returning {
+                        Some(columns) => {
+                            let rows = Self::project_returning(&schema, &columns, deleted_rows)?;
+                            Ok(ExecutionResult::Selected { columns, rows })
+                        }
+                        None => Ok(ExecutionResult::Deleted {
+                            table,
+                            rows: deleted_count,
+                        }),
+                    }
+                }
+                Statement::AlterTable { name, operations } => {
+                    let mut current_name = name;
+                    for operation in operations {
+                        match operation {
+                            AlterTableOperation::AddColumn { column } => {
+                                self.catalog.add_column(&current_name, column)?;
+                            }
+                            AlterTableOperation::DropColumn { name: column_name } => {
+                                let position =
+                                    self.catalog.drop_column(&current_name, &column_name)?;
+                                self.rewrite_rows_dropping_column(&current_name, position)?;
+                            }
+                            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                                let was_indexed = self
+                                    .catalog
+                                    .index_for_column(&current_name, &old_name)?
+                                    .is_some();
+                                let was_bitmap_indexed =
+                                    self.catalog.is_bitmap_indexed(&current_name, &old_name)?;
+                                self.catalog
+                                    .rename_column(&current_name, &old_name, &new_name)?;
+                                if was_indexed {
+                                    self.rekey_prefix(
+                                        &index::index_prefix(&current_name, &old_name),
+                                        &index::index_prefix(&current_name, &new_name),
+                                    )?;
+                                }
+                                if was_bitmap_indexed {
+                                    self.rekey_prefix(
+                                        &bitmap::posting_prefix(&current_name, &old_name),
+                                        &bitmap::posting_prefix(&current_name, &new_name),
+                                    )?;
+                                }
+                            }
+                            AlterTableOperation::RenameTable { new_name } => {
+                                let indexed_columns = self.catalog.indexed_columns(&current_name)?;
+                                let bitmap_columns =
+                                    self.catalog.bitmap_indexed_columns(&current_name)?;
+                                self.catalog.rename_table(&current_name, &new_name)?;
+                                self.rekey_prefix(
+                                    &Self::table_data_prefix(&current_name),
+                                    &Self::table_data_prefix(&new_name),
+                                )?;
+                                for column in &indexed_columns {
+                                    self.rekey_prefix(
+                                        &index::index_prefix(&current_name, column),
+                                        &index::index_prefix(&new_name, column),
+                                    )?;
+                                }
+                                for column in &bitmap_columns {
+                                    self.rekey_prefix(
+                                        &bitmap::posting_prefix(&current_name, column),
+                                        &bitmap::posting_prefix(&new_name, column),
+                                    )?;
+                                }
+                                self.rekey_prefix(
+                                    &bitmap::rowid_prefix(&current_name),
+                                    &bitmap::rowid_prefix(&new_name),
+                                )?;
+                                self.rekey_prefix(
+                                    &bitmap::rowid_rev_prefix(&current_name),
+                                    &bitmap::rowid_rev_prefix(&new_name),
+                                )?;
+                                let seq_key = bitmap::seq_key(&current_name);
+                                if let Some(seq) = self.storage.get(&seq_key)? {
+                                    self.storage.set(&bitmap::seq_key(&new_name), &seq)?;
+                                    self.storage.delete(&seq_key)?;
+                                }
+                                current_name = new_name;
+                            }
+                        }
+                    }
+                    Ok(ExecutionResult::Altered {
+                        table: current_name,
+                    })
+                }
+                Statement::CreateTableAs {
+                    name,
+                    query,
+                    temporary,
+                } => {
+                    let column_names = self.resolve_ctas_columns(&query)?;
+
+                    let rows = match self.execute(*query)? {
+                        ExecutionResult::Selected { rows, .. } => rows,
+                        _ => {
+                            return Err(StorageError::ReadError(
+                                "CREATE TABLE AS SELECT's query did not produce a result set"
+                                    .to_string(),
+                            ));
+                        }
+                    };
+
+                    let columns: Vec<Column> = column_names
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, col_name)| {
+                            let data_type = rows
+                                .first()
+                                .and_then(|row| row.values.get(i))
+                                .map(Self::infer_data_type)
+                                .unwrap_or(DataType::Null);
+                            Column::new(col_name, data_type)
+                        })
+                        .collect();
+
+                    if temporary {
+                        self.catalog
+                            .create_temp_table(&name, columns, vec![], vec![])?;
+                    } else {
+                        self.catalog.create_table(&name, columns, vec![], vec![])?;
+                    }
+
+                    let prefix = Self::table_data_prefix(&name);
+                    for row in &rows {
+                        let key = self.generate_row_key(&name);
+                        let value = serde_json::to_vec(row)?;
+                        self.storage.set(&key, &value)?;
+                        bitmap::assign_row_id(&self.storage, &name, &key[prefix.len()..])?;
+                    }
+
+                    Ok(ExecutionResult::Created { table: name })
+                }
+                Statement::Update {
+                    table,
+                    assignments,
+                    where_clause,
+                } => {
+                    let schema = self.catalog.get_table(&table)?.ok_or_else(|| {
+                        StorageError::ReadError(format!("Table {} not found", table))
+                    })?;
+
+                    let assignment_indices = assignments
+                        .iter()
+                        .map(|(column, expr)| {
+                            let idx = schema
+                                .columns
+                                .iter()
+                                .position(|c| &c.name == column)
+                                .ok_or_else(|| {
+                                    StorageError::WriteError(format!(
+                                        "Column '{}' not found",
+                                        column
+                                    ))
+                                })?;
+                            Ok((idx, expr.clone()))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+
+                    // Moving a primary-key value would leave every other
+                    // table's foreign key pointed at a row that no longer
+                    // exists under that key; NexumDB doesn't cascade a PK
+                    // change into referencing tables, so reject it outright
+                    // rather than silently orphaning those references.
+                    let assigns_primary_key = assignment_indices
+                        .iter()
+                        .any(|(idx, _)| schema.columns[*idx].primary_key);
+                    if assigns_primary_key && !self.catalog.tables_referencing(&table)?.is_empty()
+                    {
+                        return Err(StorageError::WriteError(format!(
+                            "Cannot update primary key column of table '{}': other tables reference it by foreign key",
+                            table
+                        )));
+                    }
+
+                    let prefix = Self::table_data_prefix(&table);
+                    let indexed_columns = self.catalog.indexed_columns(&table)?;
+                    let bitmap_columns = self.catalog.bitmap_indexed_columns(&table)?;
+
+                    if where_clause.is_none() {
+                        log::warn!(
+                            "UPDATE without WHERE clause will modify every row in table '{}'",
+                            table
+                        );
+                    }
+
+                    let access_path =
+                        Planner::choose_access_path(&self.catalog, &table, where_clause.as_deref())?;
+
+                    let candidates: Vec<(Vec<u8>, Vec<u8>)> = match &access_path {
+                        AccessPath::BitmapScan { predicate } => {
+                            match self.resolve_bitmap(&table, predicate)? {
+                                Some(row_ids) => row_ids
+                                    .iter()
+                                    .filter_map(|row_id| {
+                                        bitmap::row_key_for_id(&self.storage, &table, row_id)
+                                            .ok()
+                                            .flatten()
+                                    })
+                                    .filter_map(|suffix| {
+                                        let mut key = prefix.clone();
+                                        key.extend_from_slice(&suffix);
+                                        let value = self.storage.get(&key).ok()??;
+                                        Some((key, value))
+                                    })
+                                    .collect(),
+                                None => self.storage.scan_prefix(&prefix)?,
+                            }
+                        }
+                        _ => self.storage.scan_prefix(&prefix)?,
+                    };
+
+                    let column_names: Vec<String> =
+                        schema.columns.iter().map(|c| c.name.clone()).collect();
+                    let evaluator = ExpressionEvaluator::with_runner(column_names, self);
+
+                    let mut updated_count = 0;
+                    let mut change_events = Vec::new();
+                    for (key, value) in &candidates {
+                        let Ok(row) = serde_json::from_slice::<Row>(value) else {
+                            continue;
+                        };
+                        let row = Self::pad_row(&schema, row);
+
+                        if let Some(where_expr) = &where_clause {
+                            match evaluator.evaluate(where_expr, &row.values) {
+                                Ok(Some(true)) => {}
+                                Ok(Some(false)) | Ok(None) => continue,
+                                Err(e) => {
+                                    return Err(StorageError::ReadError(format!(
+                                        "WHERE clause evaluation failed: {}",
+                                        e
+                                    )));
+                                }
+                            }
+                        }
+
+                        let mut new_values = row.values.clone();
+                        for (idx, expr) in &assignment_indices {
+                            let new_value =
+                                evaluator.evaluate_scalar(expr, &row.values).map_err(|e| {
+                                    StorageError::WriteError(format!(
+                                        "SET expression evaluation failed: {}",
+                                        e
+                                    ))
+                                })?;
+                            new_values[*idx] = new_value;
+                        }
+
+                        Self::check_not_null(&schema, &new_values)?;
+                        self.check_unique_constraints(&table, &schema, &new_values, Some(key))?;
+                        self.check_foreign_keys(&schema, &new_values)?;
+
+                        let row_key_suffix = &key[prefix.len()..];
+
+                        self.update_indexes_for_row(
+                            &table,
+                            &schema,
+                            &indexed_columns,
+                            &row.values,
+                            row_key_suffix,
+                            false,
+                        )?;
+                        if let Some(row_id) = bitmap::row_id_for_key(&self.storage, &table, row_key_suffix)? {
+                            for column in &bitmap_columns {
+                                if let Some(col_idx) =
+                                    schema.columns.iter().position(|c| &c.name == column)
+                                {
+                                    bitmap::remove_row(
+                                        &self.storage,
+                                        &table,
+                                        column,
+                                        &row.values[col_idx],
+                                        row_id,
+                                    )?;
+                                    bitmap::add_row(
+                                        &self.storage,
+                                        &table,
+                                        column,
+                                        &new_values[col_idx],
+                                        row_id,
+                                    )?;
+                                }
+                            }
+                        }
+
+                        let new_row = Row {
+                            values: new_values.clone(),
+                        };
+                        self.storage.set(key, &serde_json::to_vec(&new_row)?)?;
+                        self.update_indexes_for_row(
+                            &table,
+                            &schema,
+                            &indexed_columns,
+                            &new_values,
+                            row_key_suffix,
+                            true,
+                        )?;
+
+                        change_events.push(ChangeEvent {
+                            table: table.clone(),
+                            op: ChangeOp::Update,
+                            row: new_row,
+                            old_row: Some(row),
+                        });
+                        updated_count += 1;
+                    }
+
+                    self.record_events(change_events);
+
+                    Ok(ExecutionResult::Updated {
                         table,
-                        rows: deleted_count,
+                        rows: updated_count,
+                    })
+                }
+                Statement::ShowTables => {
+                    let tables = self.catalog.list_tables()?;
+                    Ok(ExecutionResult::Tables { tables })
+                }
+                Statement::DescribeTable { name } => {
+                    let schema = self.catalog.get_table(&name)?.ok_or_else(|| {
+                        StorageError::ReadError(format!("Table {} not found", name))
+                    })?;
+                    Ok(ExecutionResult::Described {
+                        table: name,
+                        columns: schema.columns,
                     })
                 }
+                Statement::DropTable { name, if_exists } => {
+                    if !if_exists && self.catalog.get_table(&name)?.is_none() {
+                        return Err(StorageError::ReadError(format!(
+                            "Table {} not found",
+                            name
+                        )));
+                    }
+                    self.catalog.drop_table(&name)?;
+                    Ok(ExecutionResult::Dropped { table: name })
+                }
+                Statement::CreateIndex { name, table, column } => {
+                    let schema = self.catalog.get_table(&table)?.ok_or_else(|| {
+                        StorageError::ReadError(format!("Table {} not found", table))
+                    })?;
+                    let col_idx = schema
+                        .columns
+                        .iter()
+                        .position(|c| c.name == column)
+                        .ok_or_else(|| {
+                            StorageError::ReadError(format!("Column {} not found", column))
+                        })?;
+
+                    self.catalog.create_bitmap_index(&table, &column)?;
+
+                    let prefix = Self::table_data_prefix(&table);
+                    for (key, value) in self.storage.scan_prefix(&prefix)? {
+                        let Ok(row) = serde_json::from_slice::<Row>(&value) else {
+                            continue;
+                        };
+                        let row = Self::pad_row(&schema, row);
+                        let row_key_suffix = &key[prefix.len()..];
+                        if let Some(row_id) = bitmap::row_id_for_key(&self.storage, &table, row_key_suffix)? {
+                            bitmap::add_row(&self.storage, &table, &column, &row.values[col_idx], row_id)?;
+                        }
+                    }
+
+                    Ok(ExecutionResult::IndexCreated { name, table, column })
+                }
+                Statement::Explain { query } => {
+                    let rows = Planner::explain(&self.catalog, *query)?;
+                    Ok(ExecutionResult::Explain(Self::format_explain(&rows)))
+                }
+                Statement::Begin => {
+                    self.storage.begin()?;
+                    *self.pending_events.lock().unwrap() = Some(Vec::new());
+                    Ok(ExecutionResult::TransactionStarted)
+                }
+                Statement::Commit => {
+                    self.storage.commit()?;
+                    let events = self.pending_events.lock().unwrap().take().unwrap_or_default();
+                    self.dispatch_events(&events);
+                    Ok(ExecutionResult::TransactionCommitted)
+                }
+                Statement::Rollback => {
+                    self.storage.rollback()?;
+                    *self.pending_events.lock().unwrap() = None;
+                    Ok(ExecutionResult::TransactionRolledBack)
+                }
             };
 
         let duration = start.elapsed();
@@ -226,6 +1144,45 @@ impl Executor {
         result
     }
 
+    /// Binds `args` into `prepared`'s placeholders and executes the result, so a
+    /// statement parsed once (e.g. via `StatementCache::allocate`) can be run
+    /// with different values without re-parsing its SQL text.
+    pub fn execute_prepared(
+        &self,
+        prepared: &crate::sql::PreparedStatement,
+        args: Vec<Value>,
+    ) -> Result<ExecutionResult> {
+        let statement = prepared
+            .bind(&args)
+            .map_err(|e| StorageError::ReadError(e.to_string()))?;
+        self.execute(statement)
+    }
+
+    /// Runs `f` as one atomic, isolated block: every statement `f` executes
+    /// against this `Executor` is staged by `StorageEngine::begin` and, once `f`
+    /// returns `Ok`, applied all at once by `commit`; an `Err` (from `f` itself
+    /// or from any statement it ran) discards the whole block via `rollback`
+    /// instead. A `BEGIN`/`COMMIT`/`ROLLBACK` triple run through `execute`
+    /// achieves the same thing for a statement-at-a-time caller (e.g. a REPL);
+    /// this is the programmatic, single-call equivalent.
+    pub fn transaction<F, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Executor) -> Result<T>,
+    {
+        *self.pending_events.lock().unwrap() = Some(Vec::new());
+        match self.storage.transaction(|_| f(self)) {
+            Ok(value) => {
+                let events = self.pending_events.lock().unwrap().take().unwrap_or_default();
+                self.dispatch_events(&events);
+                Ok(value)
+            }
+            Err(e) => {
+                *self.pending_events.lock().unwrap() = None;
+                Err(e)
+            }
+        }
+    }
+
     fn generate_row_key(&self, table: &str) -> Vec<u8> {
         use std::time::{SystemTime, UNIX_EPOCH};
         let timestamp = SystemTime::now()
@@ -242,166 +1199,2085 @@ impl Executor {
         format!("data:{}:", table).into_bytes()
     }
 
-    pub fn save_cache(&self) -> Result<()> {
-        if let Some(cache) = &self.cache {
-            cache
-                .save_cache()
-                .map_err(|e| StorageError::WriteError(e.to_string()))?;
-            println!("Semantic cache saved to disk");
-        } else {
-            println!("No semantic cache to save");
+    /// Pads a row stored before a later `ADD COLUMN` with each missing trailing
+    /// column's default (or `NULL`), so callers can always index `row.values` by
+    /// the current schema's column positions. `ADD COLUMN` only ever appends, so
+    /// a short row is always missing values off its end, never in the middle.
+    fn pad_row(schema: &TableSchema, mut row: Row) -> Row {
+        while row.values.len() < schema.columns.len() {
+            let default = schema.columns[row.values.len()]
+                .default
+                .clone()
+                .unwrap_or(Value::Null);
+            row.values.push(default);
         }
-        Ok(())
+        row
     }
 
-    pub fn clear_cache(&self) -> Result<()> {
-        if let Some(cache) = &self.cache {
-            cache
-                .clear_cache()
-                .map_err(|e| StorageError::WriteError(e.to_string()))?;
-            println!("Semantic cache cleared");
+    /// Derives a `CREATE TABLE ... AS SELECT`'s output column names from its inner
+    /// query's projection. A bare `SELECT *` has no projection labels of its own
+    /// (`resolve_projection_labels` reports a single `"*"` placeholder), so that
+    /// case is resolved against the source table's schema instead.
+    fn resolve_ctas_columns(&self, query: &Statement) -> Result<Vec<String>> {
+        let Statement::Select {
+            table, projection, ..
+        } = query
+        else {
+            return Err(StorageError::ReadError(
+                "CREATE TABLE AS SELECT only supports a SELECT query".to_string(),
+            ));
+        };
+
+        if matches!(projection.as_slice(), [SelectItem::Wildcard]) {
+            let schema = self.catalog.get_table(table)?.ok_or_else(|| {
+                StorageError::ReadError(format!("Table {} not found", table))
+            })?;
+            Ok(schema.columns.into_iter().map(|c| c.name).collect())
         } else {
-            println!("No semantic cache to clear");
+            Ok(Self::resolve_projection_labels(projection))
         }
-        Ok(())
     }
 
-    pub fn get_cache_stats(&self) -> Result<String> {
-        if let Some(cache) = &self.cache {
-            cache
-                .get_cache_stats()
-                .map_err(|e| StorageError::ReadError(e.to_string()))
-        } else {
-            Ok("No semantic cache enabled".to_string())
+    /// Infers a materialized column's `DataType` from one of its values, used to
+    /// build a schema for `CREATE TABLE ... AS SELECT` since it declares no
+    /// column types of its own.
+    fn infer_data_type(value: &Value) -> DataType {
+        match value {
+            Value::Integer(_) => DataType::Integer,
+            Value::Float(_) => DataType::Float,
+            Value::Text(_) => DataType::Text,
+            Value::Boolean(_) => DataType::Boolean,
+            Value::Json(_) => DataType::Json,
+            Value::Null | Value::Placeholder(_) => DataType::Null,
         }
     }
-}
 
-impl Clone for StorageEngine {
-    fn clone(&self) -> Self {
-        StorageEngine::memory().unwrap()
+    /// Expands an `INSERT`'s `(columns, row_values)` into a full, schema-ordered
+    /// row: when `columns` is empty, `row_values` is assumed to already be in
+    /// schema order; otherwise every schema column absent from `columns` is
+    /// filled with its `DEFAULT` (or `NULL` if it has none).
+    fn resolve_insert_row(
+        schema: &crate::sql::types::TableSchema,
+        columns: &[String],
+        row_values: &[Value],
+    ) -> Result<Vec<Value>> {
+        if columns.is_empty() {
+            return Ok(row_values.to_vec());
+        }
+
+        if columns.len() != row_values.len() {
+            return Err(StorageError::WriteError(format!(
+                "INSERT column list has {} columns but {} values were given",
+                columns.len(),
+                row_values.len()
+            )));
+        }
+
+        schema
+            .columns
+            .iter()
+            .map(
+                |column| match columns.iter().position(|c| c == &column.name) {
+                    Some(idx) => Ok(row_values[idx].clone()),
+                    None => Ok(column.default.clone().unwrap_or(Value::Null)),
+                },
+            )
+            .collect()
     }
-}
 
-#[derive(Debug)]
-pub enum ExecutionResult {
-    Created {
-        table: String,
-    },
-    Inserted {
-        table: String,
+    fn check_not_null(schema: &crate::sql::types::TableSchema, row_values: &[Value]) -> Result<()> {
+        for (column, value) in schema.columns.iter().zip(row_values) {
+            if column.not_null && matches!(value, Value::Null) {
+                return Err(StorageError::WriteError(format!(
+                    "Column '{}' cannot be NULL",
+                    column.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolves each name in `names` to its position among `schema`'s columns.
+    fn column_indices(
+        schema: &crate::sql::types::TableSchema,
+        names: &[String],
+    ) -> Result<Vec<usize>> {
+        names
+            .iter()
+            .map(|name| {
+                schema
+                    .columns
+                    .iter()
+                    .position(|c| &c.name == name)
+                    .ok_or_else(|| StorageError::ReadError(format!("Column {} not found", name)))
+            })
+            .collect()
+    }
+
+    /// Rejects an insert that would duplicate an existing row's values across any
+    /// of `schema.unique_groups` (from a `PRIMARY KEY`/`UNIQUE` column or table
+    /// constraint). A group containing a NULL is exempt, per standard SQL UNIQUE
+    /// semantics. `exclude_key` skips a row's own storage key, so an `UPDATE` that
+    /// leaves a unique column unchanged isn't flagged as colliding with itself.
+    fn check_unique_constraints(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        row_values: &[Value],
+        exclude_key: Option<&[u8]>,
+    ) -> Result<()> {
+        if schema.unique_groups.is_empty() {
+            return Ok(());
+        }
+
+        let prefix = Self::table_data_prefix(table);
+        let existing_rows = self.storage.scan_prefix(&prefix)?;
+
+        for group in &schema.unique_groups {
+            let indices = Self::column_indices(schema, group)?;
+            if indices.iter().any(|&i| matches!(row_values[i], Value::Null)) {
+                continue;
+            }
+
+            let duplicate = existing_rows.iter().any(|(key, value)| {
+                if Some(key.as_slice()) == exclude_key {
+                    return false;
+                }
+                serde_json::from_slice::<Row>(value)
+                    .map(|existing| Self::pad_row(schema, existing))
+                    .map(|existing| {
+                        indices
+                            .iter()
+                            .all(|&i| filter::values_equal(&existing.values[i], &row_values[i]))
+                    })
+                    .unwrap_or(false)
+            });
+
+            if duplicate {
+                return Err(StorageError::WriteError(format!(
+                    "Duplicate value violates unique constraint on ({})",
+                    group.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds an existing row colliding with `row_values` on one of
+    /// `schema.unique_groups`, for `ON CONFLICT REPLACE` to overwrite in place
+    /// instead of failing `check_unique_constraints`. Returns the first
+    /// colliding group's row (key plus its current, padded values).
+    fn find_unique_conflict(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        row_values: &[Value],
+    ) -> Result<Option<(Vec<u8>, Row)>> {
+        if schema.unique_groups.is_empty() {
+            return Ok(None);
+        }
+
+        let prefix = Self::table_data_prefix(table);
+        let existing_rows = self.storage.scan_prefix(&prefix)?;
+
+        for group in &schema.unique_groups {
+            let indices = Self::column_indices(schema, group)?;
+            if indices.iter().any(|&i| matches!(row_values[i], Value::Null)) {
+                continue;
+            }
+
+            let hit = existing_rows.iter().find_map(|(key, value)| {
+                let existing = Self::pad_row(schema, serde_json::from_slice::<Row>(value).ok()?);
+                indices
+                    .iter()
+                    .all(|&i| filter::values_equal(&existing.values[i], &row_values[i]))
+                    .then(|| (key.clone(), existing))
+            });
+
+            if hit.is_some() {
+                return Ok(hit);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Rejects an insert whose foreign-key columns don't match any row in the
+    /// referenced table. A row with a NULL in any of the FK's columns is exempt.
+    fn check_foreign_keys(
+        &self,
+        schema: &crate::sql::types::TableSchema,
+        row_values: &[Value],
+    ) -> Result<()> {
+        for fk in &schema.foreign_keys {
+            let indices = Self::column_indices(schema, &fk.columns)?;
+            if indices.iter().any(|&i| matches!(row_values[i], Value::Null)) {
+                continue;
+            }
+
+            let ref_schema = self.catalog.get_table(&fk.ref_table)?.ok_or_else(|| {
+                StorageError::WriteError(format!(
+                    "Foreign key references unknown table '{}'",
+                    fk.ref_table
+                ))
+            })?;
+            let ref_indices = Self::column_indices(&ref_schema, &fk.ref_columns)?;
+
+            let ref_rows = self.storage.scan_prefix(&Self::table_data_prefix(&fk.ref_table))?;
+            let found = ref_rows.iter().any(|(_, value)| {
+                serde_json::from_slice::<Row>(value)
+                    .map(|ref_row| Self::pad_row(&ref_schema, ref_row))
+                    .map(|ref_row| {
+                        indices
+                            .iter()
+                            .zip(&ref_indices)
+                            .all(|(&i, &ri)| filter::values_equal(&row_values[i], &ref_row.values[ri]))
+                    })
+                    .unwrap_or(false)
+            });
+
+            if !found {
+                return Err(StorageError::WriteError(format!(
+                    "Foreign key violation: no row in '{}' matches columns ({})",
+                    fk.ref_table,
+                    fk.columns.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies a parent row's `FOREIGN KEY ... ON DELETE` action to every table
+    /// that references `table`, before the parent row itself is removed: blocks
+    /// the delete (`Restrict`/`NoAction`), removes referencing rows (`Cascade`),
+    /// or clears their FK columns (`SetNull`/`SetDefault`). Every row the
+    /// cascade touches gets a `ChangeEvent` appended to `change_events`, so
+    /// callers can dispatch them together with the parent delete's own events.
+    fn enforce_fk_on_delete(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        parent_row: &[Value],
+        change_events: &mut Vec<ChangeEvent>,
+    ) -> Result<()> {
+        let mut visited = std::collections::HashSet::new();
+        visited.insert(table.to_string());
+        self.enforce_fk_on_delete_visited(table, schema, parent_row, &visited, change_events)
+    }
+
+    /// Recursive core of [`Self::enforce_fk_on_delete`]: `visited` carries every
+    /// table already on the path from the original delete down to `table`, so a
+    /// self-referencing or mutually-referencing set of FKs (`A -> B -> A`) stops
+    /// once the cascade comes back around instead of recursing forever. Each
+    /// branch gets its own clone, so cascading the same child table from two
+    /// different parent rows (not a cycle) still recurses for both.
+    fn enforce_fk_on_delete_visited(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        parent_row: &[Value],
+        visited: &std::collections::HashSet<String>,
+        change_events: &mut Vec<ChangeEvent>,
+    ) -> Result<()> {
+        use crate::sql::types::ReferentialAction;
+
+        for (child_table, fk) in self.catalog.tables_referencing(table)? {
+            let ref_indices = Self::column_indices(schema, &fk.ref_columns)?;
+            let parent_values: Vec<Value> =
+                ref_indices.iter().map(|&i| parent_row[i].clone()).collect();
+
+            if parent_values.iter().any(|v| matches!(v, Value::Null)) {
+                continue;
+            }
+
+            let child_schema = self.catalog.get_table(&child_table)?.ok_or_else(|| {
+                StorageError::ReadError(format!("Table {} not found", child_table))
+            })?;
+            let fk_indices = Self::column_indices(&child_schema, &fk.columns)?;
+            let child_prefix = Self::table_data_prefix(&child_table);
+            let child_indexed = self.catalog.indexed_columns(&child_table)?;
+            let child_bitmap_indexed = self.catalog.bitmap_indexed_columns(&child_table)?;
+
+            let matching: Vec<(Vec<u8>, Row)> = self
+                .storage
+                .scan_prefix(&child_prefix)?
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let row = serde_json::from_slice::<Row>(&value).ok()?;
+                    let row = Self::pad_row(&child_schema, row);
+                    let is_match = fk_indices
+                        .iter()
+                        .zip(&parent_values)
+                        .all(|(&i, v)| filter::values_equal(&row.values[i], v));
+                    is_match.then_some((key, row))
+                })
+                .collect();
+
+            if matching.is_empty() {
+                continue;
+            }
+
+            match fk.on_delete {
+                ReferentialAction::Restrict | ReferentialAction::NoAction => {
+                    return Err(StorageError::WriteError(format!(
+                        "Cannot delete from '{}': referenced by {} row(s) in '{}'",
+                        table,
+                        matching.len(),
+                        child_table
+                    )));
+                }
+                ReferentialAction::Cascade => {
+                    for (key, row) in &matching {
+                        if !visited.contains(&child_table) {
+                            let mut child_visited = visited.clone();
+                            child_visited.insert(child_table.clone());
+                            self.enforce_fk_on_delete_visited(
+                                &child_table,
+                                &child_schema,
+                                &row.values,
+                                &child_visited,
+                                change_events,
+                            )?;
+                        }
+                        self.storage.delete(key)?;
+                        let row_key_suffix = &key[child_prefix.len()..];
+                        self.update_indexes_for_row(
+                            &child_table,
+                            &child_schema,
+                            &child_indexed,
+                            &row.values,
+                            row_key_suffix,
+                            false,
+                        )?;
+                        self.update_bitmap_indexes_for_delete(
+                            &child_table,
+                            &child_schema,
+                            &child_bitmap_indexed,
+                            &row.values,
+                            row_key_suffix,
+                        )?;
+                        change_events.push(ChangeEvent {
+                            table: child_table.clone(),
+                            op: ChangeOp::Delete,
+                            row: row.clone(),
+                            old_row: None,
+                        });
+                    }
+                }
+                ReferentialAction::SetNull | ReferentialAction::SetDefault => {
+                    for (key, row) in &matching {
+                        let mut new_values = row.values.clone();
+                        for (&i, col_name) in fk_indices.iter().zip(&fk.columns) {
+                            new_values[i] = if fk.on_delete == ReferentialAction::SetDefault {
+                                child_schema
+                                    .columns
+                                    .iter()
+                                    .find(|c| &c.name == col_name)
+                                    .and_then(|c| c.default.clone())
+                                    .unwrap_or(Value::Null)
+                            } else {
+                                Value::Null
+                            };
+                        }
+
+                        let row_key_suffix = &key[child_prefix.len()..];
+                        self.update_indexes_for_row(
+                            &child_table,
+                            &child_schema,
+                            &child_indexed,
+                            &row.values,
+                            row_key_suffix,
+                            false,
+                        )?;
+                        self.storage
+                            .set(key, &serde_json::to_vec(&Row { values: new_values.clone() })?)?;
+                        self.update_indexes_for_row(
+                            &child_table,
+                            &child_schema,
+                            &child_indexed,
+                            &new_values,
+                            row_key_suffix,
+                            true,
+                        )?;
+
+                        if !child_bitmap_indexed.is_empty() {
+                            let row_id = bitmap::row_id_for_key(&self.storage, &child_table, row_key_suffix)?;
+                            if let Some(row_id) = row_id {
+                                for column in &child_bitmap_indexed {
+                                    let Some(col_idx) =
+                                        child_schema.columns.iter().position(|c| &c.name == column)
+                                    else {
+                                        continue;
+                                    };
+                                    bitmap::remove_row(
+                                        &self.storage,
+                                        &child_table,
+                                        column,
+                                        &row.values[col_idx],
+                                        row_id,
+                                    )?;
+                                    bitmap::add_row(
+                                        &self.storage,
+                                        &child_table,
+                                        column,
+                                        &new_values[col_idx],
+                                        row_id,
+                                    )?;
+                                }
+                            }
+                        }
+
+                        change_events.push(ChangeEvent {
+                            table: child_table.clone(),
+                            op: ChangeOp::Update,
+                            row: Row { values: new_values },
+                            old_row: Some(row.clone()),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_cache(&self) -> Result<()> {
+        if let Some(cache) = &self.cache {
+            cache
+                .save_cache()
+                .map_err(|e| StorageError::WriteError(e.to_string()))?;
+            println!("Semantic cache saved to disk");
+        } else {
+            println!("No semantic cache to save");
+        }
+        Ok(())
+    }
+
+    pub fn clear_cache(&self) -> Result<()> {
+        if let Some(cache) = &self.cache {
+            cache
+                .clear_cache()
+                .map_err(|e| StorageError::WriteError(e.to_string()))?;
+            println!("Semantic cache cleared");
+        } else {
+            println!("No semantic cache to clear");
+        }
+        Ok(())
+    }
+
+    pub fn get_cache_stats(&self) -> Result<String> {
+        if let Some(cache) = &self.cache {
+            cache
+                .get_cache_stats()
+                .map_err(|e| StorageError::ReadError(e.to_string()))
+        } else {
+            Ok("No semantic cache enabled".to_string())
+        }
+    }
+
+    /// Adds or removes the index entries for one row across every indexed column,
+    /// keeping each index's sled-backed keyspace consistent with the table data.
+    fn update_indexes_for_row(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        indexed_columns: &[String],
+        row_values: &[Value],
+        row_key_suffix: &[u8],
+        insert: bool,
+    ) -> Result<()> {
+        for column in indexed_columns {
+            let Some(col_idx) = schema.columns.iter().position(|c| &c.name == column) else {
+                continue;
+            };
+            let Some(entry) = index::entry_key(table, column, &row_values[col_idx], row_key_suffix)
+            else {
+                continue;
+            };
+
+            if insert {
+                self.storage.set(&entry, &[])?;
+            } else {
+                self.storage.delete(&entry)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Assigns `row_key_suffix` a row-id (every insert gets one, regardless of
+    /// whether `table` has a bitmap index yet) and adds it to each bitmap-indexed
+    /// column's posting list.
+    fn update_bitmap_indexes_for_insert(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        bitmap_columns: &[String],
+        row_values: &[Value],
+        row_key_suffix: &[u8],
+    ) -> Result<()> {
+        let row_id = bitmap::assign_row_id(&self.storage, table, row_key_suffix)?;
+        for column in bitmap_columns {
+            let Some(col_idx) = schema.columns.iter().position(|c| &c.name == column) else {
+                continue;
+            };
+            bitmap::add_row(&self.storage, table, column, &row_values[col_idx], row_id)?;
+        }
+        Ok(())
+    }
+
+    /// Removes `row_key_suffix`'s row-id from each bitmap-indexed column's posting
+    /// list and forgets the row-id mapping itself. A no-op if the row was never
+    /// assigned a row-id (shouldn't happen, since every insert assigns one).
+    fn update_bitmap_indexes_for_delete(
+        &self,
+        table: &str,
+        schema: &crate::sql::types::TableSchema,
+        bitmap_columns: &[String],
+        row_values: &[Value],
+        row_key_suffix: &[u8],
+    ) -> Result<()> {
+        let Some(row_id) = bitmap::row_id_for_key(&self.storage, table, row_key_suffix)? else {
+            return Ok(());
+        };
+        for column in bitmap_columns {
+            let Some(col_idx) = schema.columns.iter().position(|c| &c.name == column) else {
+                continue;
+            };
+            bitmap::remove_row(&self.storage, table, column, &row_values[col_idx], row_id)?;
+        }
+        bitmap::forget_row_id(&self.storage, table, row_id, row_key_suffix)
+    }
+
+    /// Resolves a `Predicate` tree (every leaf already known bitmap-indexable, via
+    /// `Planner::bitmap_resolvable`) into the set of matching row-ids, intersecting
+    /// `AND`s and unioning `OR`s. Returns `None` if a leaf value has no
+    /// order-preserving encoding, signaling the caller to fall back to a full scan.
+    fn resolve_bitmap(&self, table: &str, predicate: &Predicate) -> Result<Option<RoaringBitmap>> {
+        match predicate {
+            Predicate::Comparison {
+                column,
+                op: CompareOp::Eq,
+                value,
+            } => bitmap::eq_bitmap(&self.storage, table, column, value),
+            Predicate::In {
+                column,
+                values,
+                negated: false,
+            } => {
+                let mut union = RoaringBitmap::new();
+                for value in values {
+                    match bitmap::eq_bitmap(&self.storage, table, column, value)? {
+                        Some(bm) => union |= bm,
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some(union))
+            }
+            Predicate::Between {
+                column,
+                low,
+                high,
+                negated: false,
+            } => bitmap::range_union(&self.storage, table, column, low, high),
+            Predicate::And(left, right) => {
+                match (
+                    self.resolve_bitmap(table, left)?,
+                    self.resolve_bitmap(table, right)?,
+                ) {
+                    (Some(l), Some(r)) => Ok(Some(l & r)),
+                    _ => Ok(None),
+                }
+            }
+            Predicate::Or(left, right) => {
+                match (
+                    self.resolve_bitmap(table, left)?,
+                    self.resolve_bitmap(table, right)?,
+                ) {
+                    (Some(l), Some(r)) => Ok(Some(l | r)),
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Moves every entry under `old_prefix` to the same suffix under `new_prefix`,
+    /// used by `RENAME TABLE`/`RENAME COLUMN` to re-key the physical `data:`/`idx:`
+    /// storage to match a catalog rename.
+    fn rekey_prefix(&self, old_prefix: &[u8], new_prefix: &[u8]) -> Result<()> {
+        for (key, value) in self.storage.scan_prefix(old_prefix)? {
+            let suffix = &key[old_prefix.len()..];
+            let mut new_key = new_prefix.to_vec();
+            new_key.extend_from_slice(suffix);
+            self.storage.set(&new_key, &value)?;
+            self.storage.delete(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Eagerly splices the dropped column's value out of every stored row of
+    /// `table`, since (unlike `ADD COLUMN`) a dropped column isn't necessarily
+    /// last, so leaving rows as-is would misalign every column after it.
+    fn rewrite_rows_dropping_column(&self, table: &str, position: usize) -> Result<()> {
+        let prefix = Self::table_data_prefix(table);
+        for (key, value) in self.storage.scan_prefix(&prefix)? {
+            if let Ok(mut row) = serde_json::from_slice::<Row>(&value) {
+                if position < row.values.len() {
+                    row.values.remove(position);
+                }
+                self.storage.set(&key, &serde_json::to_vec(&row)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the rows an index scan narrows to: a byte-range scan over the index's
+    /// keyspace followed by a point read of each matching row.
+    fn scan_index(
+        &self,
+        table: &str,
+        index_column: &str,
+        bounds: &IndexBounds,
+        data_prefix: &[u8],
+    ) -> Result<Vec<Row>> {
+        let range = match bounds {
+            IndexBounds::Eq(value) => index::eq_range(table, index_column, value),
+            IndexBounds::Range { lower, upper } => {
+                index::bounds_range(table, index_column, lower.as_ref(), upper.as_ref())
+            }
+        };
+
+        let Some((start, end)) = range else {
+            return Ok(Vec::new());
+        };
+
+        let entries = self.storage.scan_range(start..end)?;
+        let mut rows = Vec::with_capacity(entries.len());
+        for (entry_key, _) in entries {
+            let row_key_suffix = index::row_key_from_entry(&entry_key);
+            let mut row_key = data_prefix.to_vec();
+            row_key.extend_from_slice(&row_key_suffix);
+
+            if let Some(value) = self.storage.get(&row_key)? {
+                if let Ok(row) = serde_json::from_slice::<Row>(&value) {
+                    rows.push(row);
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Renders `Planner::explain`'s output as an indented tree, one line per
+    /// node, the way a REPL would print `EXPLAIN`'s result: depth is the
+    /// number of `parent_id` hops back to a root (`parent_id: None`).
+    fn format_explain(rows: &[ExplainRow]) -> String {
+        let depth_of = |id: usize| -> usize {
+            let mut depth = 0;
+            let mut current = id;
+            while let Some(parent_id) = rows
+                .iter()
+                .find(|row| row.id == current)
+                .and_then(|row| row.parent_id)
+            {
+                depth += 1;
+                current = parent_id;
+            }
+            depth
+        };
+
+        rows.iter()
+            .map(|row| {
+                format!(
+                    "{}{} (rows={}, cost={:.2})",
+                    "  ".repeat(depth_of(row.id)),
+                    row.operation,
+                    row.estimated_rows,
+                    row.estimated_cost
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Resolves the human-readable column labels a `SELECT` reports for its
+    /// projection: `Wildcard` is rendered as `*`, named and computed columns use
+    /// their alias when present (falling back to the column name or, for a computed
+    /// expression, its SQL text).
+    fn resolve_projection_labels(projection: &[SelectItem]) -> Vec<String> {
+        projection
+            .iter()
+            .map(|item| match item {
+                SelectItem::Wildcard => "*".to_string(),
+                SelectItem::Column { name, alias } => alias.clone().unwrap_or_else(|| name.clone()),
+                SelectItem::Expr { expr, alias } => {
+                    alias.clone().unwrap_or_else(|| expr.to_string())
+                }
+                SelectItem::Aggregate {
+                    func,
+                    column,
+                    alias,
+                } => alias.clone().unwrap_or_else(|| match column {
+                    Some(col) => format!("{}({})", Self::aggregate_func_name(*func), col),
+                    None => format!("{}(*)", Self::aggregate_func_name(*func)),
+                }),
+            })
+            .collect()
+    }
+
+    /// True if any projection item computes an aggregate (`COUNT`, `SUM`, etc.),
+    /// meaning the `SELECT` must go through `aggregate_rows` rather than a plain
+    /// per-row projection.
+    fn has_aggregates(projection: &[SelectItem]) -> bool {
+        projection
+            .iter()
+            .any(|item| matches!(item, SelectItem::Aggregate { .. }))
+    }
+
+    fn aggregate_func_name(func: AggregateFunc) -> &'static str {
+        match func {
+            AggregateFunc::Count => "COUNT",
+            AggregateFunc::Sum => "SUM",
+            AggregateFunc::Avg => "AVG",
+            AggregateFunc::Min => "MIN",
+            AggregateFunc::Max => "MAX",
+        }
+    }
+
+    /// Projects an `INSERT`/`DELETE ... RETURNING`'s affected rows down to the
+    /// requested column list, mirroring `SELECT`'s plain-`Column` projection.
+    fn project_returning(
+        schema: &crate::sql::types::TableSchema,
+        columns: &[String],
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>> {
+        let indices = Self::column_indices(schema, columns)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Row {
+                values: indices.iter().map(|&i| row.values[i].clone()).collect(),
+            })
+            .collect())
+    }
+
+    /// Builds the final output rows for a `SELECT`, expanding each projection item:
+    /// `Wildcard` keeps the full row, `Column` picks out a single stored value, and
+    /// `Expr` computes a scalar via `ExpressionEvaluator::evaluate_scalar`.
+    fn project_rows(
+        projection: &[SelectItem],
+        schema: &crate::sql::types::TableSchema,
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>> {
+        if matches!(projection, [SelectItem::Wildcard]) {
+            return Ok(rows);
+        }
+
+        let column_names: Vec<String> = schema.columns.iter().map(|c| c.name.clone()).collect();
+        let evaluator = ExpressionEvaluator::new(column_names.clone());
+
+        rows.into_iter()
+            .map(|row| {
+                let mut values = Vec::with_capacity(projection.len());
+                for item in projection {
+                    match item {
+                        SelectItem::Wildcard => values.extend(row.values.clone()),
+                        SelectItem::Column { name, .. } => {
+                            let idx = column_names
+                                .iter()
+                                .position(|c| c == name)
+                                .ok_or_else(|| StorageError::ReadError(format!(
+                                    "Column {} not found",
+                                    name
+                                )))?;
+                            values.push(row.values[idx].clone());
+                        }
+                        SelectItem::Expr { expr, .. } => {
+                            let value = evaluator.evaluate_scalar(expr, &row.values).map_err(|e| {
+                                StorageError::ReadError(format!(
+                                    "Failed to evaluate projected expression: {}",
+                                    e
+                                ))
+                            })?;
+                            values.push(value);
+                        }
+                        SelectItem::Aggregate { .. } => {
+                            return Err(StorageError::ReadError(
+                                "Aggregate projections require GROUP BY handling".to_string(),
+                            ));
+                        }
+                    }
+                }
+                Ok(Row { values })
+            })
+            .collect()
+    }
+
+    /// Groups `rows` by the tuple of `group_by` column values, folds each
+    /// projection's aggregates over its group, applies HAVING, and emits one row
+    /// per surviving group. Called whenever the projection has an aggregate or the
+    /// query has a `GROUP BY`, instead of `project_rows`.
+    fn aggregate_rows(
+        schema: &crate::sql::types::TableSchema,
+        projection: &[SelectItem],
+        group_by: &[String],
+        having: Option<&sqlparser::ast::Expr>,
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>> {
+        let column_names: Vec<String> = schema.columns.iter().map(|c| c.name.clone()).collect();
+
+        let group_indices = group_by
+            .iter()
+            .map(|col| {
+                column_names
+                    .iter()
+                    .position(|c| c == col)
+                    .ok_or_else(|| StorageError::ReadError(format!("Column {} not found", col)))
+            })
+            .collect::<Result<Vec<usize>>>()?;
+
+        Self::validate_aggregate_projection(projection, group_by)?;
+
+        let mut order: Vec<String> = Vec::new();
+        let mut groups: std::collections::HashMap<String, (Vec<Value>, Vec<Row>)> =
+            std::collections::HashMap::new();
+
+        for row in rows {
+            let key_values: Vec<Value> = group_indices.iter().map(|&i| row.values[i].clone()).collect();
+            let key = serde_json::to_string(&key_values).unwrap_or_default();
+
+            let group = groups.entry(key.clone()).or_insert_with(|| {
+                order.push(key.clone());
+                (key_values, Vec::new())
+            });
+            group.1.push(row);
+        }
+
+        // An aggregate with no GROUP BY still reports one row even when zero input
+        // rows matched (e.g. `SELECT COUNT(*) FROM t WHERE 1 = 0` is `0`, not empty).
+        if groups.is_empty() && group_by.is_empty() {
+            order.push(String::new());
+            groups.insert(String::new(), (Vec::new(), Vec::new()));
+        }
+
+        let mut out = Vec::with_capacity(order.len());
+        for key in order {
+            let (key_values, group_rows) = &groups[&key];
+
+            if let Some(having_expr) = having {
+                if !Self::evaluate_having(having_expr, &column_names, group_by, key_values, group_rows)?
+                {
+                    continue;
+                }
+            }
+
+            let mut values = Vec::with_capacity(projection.len());
+            for item in projection {
+                match item {
+                    SelectItem::Aggregate { func, column, .. } => {
+                        values.push(Self::fold_aggregate(
+                            *func,
+                            column.as_deref(),
+                            &column_names,
+                            group_rows,
+                        )?);
+                    }
+                    SelectItem::Column { name, .. } => {
+                        let pos = group_by.iter().position(|g| g == name).ok_or_else(|| {
+                            StorageError::ReadError(format!(
+                                "Column '{}' must appear in GROUP BY or be used in an aggregate function",
+                                name
+                            ))
+                        })?;
+                        values.push(key_values[pos].clone());
+                    }
+                    SelectItem::Wildcard => {
+                        return Err(StorageError::ReadError(
+                            "SELECT * cannot be combined with GROUP BY".to_string(),
+                        ));
+                    }
+                    SelectItem::Expr { .. } => {
+                        return Err(StorageError::ReadError(
+                            "Computed expressions are not yet supported alongside GROUP BY".to_string(),
+                        ));
+                    }
+                }
+            }
+            out.push(Row { values });
+        }
+
+        Ok(out)
+    }
+
+    /// A bare column in a projection alongside an aggregate must be one of the
+    /// GROUP BY columns, otherwise its value within a group is ambiguous. This also
+    /// covers the no-`GROUP BY` case, since every bare column then fails the check.
+    fn validate_aggregate_projection(projection: &[SelectItem], group_by: &[String]) -> Result<()> {
+        if !Self::has_aggregates(projection) {
+            return Ok(());
+        }
+
+        for item in projection {
+            if let SelectItem::Column { name, .. } = item {
+                if !group_by.iter().any(|g| g == name) {
+                    return Err(StorageError::ReadError(format!(
+                        "Column '{}' must appear in GROUP BY or be used in an aggregate function",
+                        name
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Folds one aggregate over a group's member rows. `column` is `None` only for
+    /// `COUNT(*)`, which counts every row including ones with NULL columns; every
+    /// other aggregate skips NULLs.
+    fn fold_aggregate(
+        func: AggregateFunc,
+        column: Option<&str>,
+        column_names: &[String],
+        group_rows: &[Row],
+    ) -> Result<Value> {
+        let col_idx = column
+            .map(|name| {
+                column_names
+                    .iter()
+                    .position(|c| c == name)
+                    .ok_or_else(|| StorageError::ReadError(format!("Column {} not found", name)))
+            })
+            .transpose()?;
+
+        let mut acc = Accumulator::default();
+        for row in group_rows {
+            match col_idx {
+                None => acc.accumulate_star(),
+                Some(idx) => acc.accumulate(&row.values[idx]),
+            }
+        }
+
+        Ok(acc.finish(func))
+    }
+
+    /// Evaluates a `HAVING` predicate against one group: aggregate calls within it
+    /// are folded over the group's rows and replaced by their resulting literal, and
+    /// the rewritten expression (now free of aggregates) is evaluated as an ordinary
+    /// WHERE-style predicate against the group's key columns.
+    fn evaluate_having(
+        having: &sqlparser::ast::Expr,
+        column_names: &[String],
+        group_by: &[String],
+        key_values: &[Value],
+        group_rows: &[Row],
+    ) -> Result<bool> {
+        let rewritten = Self::rewrite_aggregates(having, column_names, group_rows).map_err(|e| {
+            StorageError::ReadError(format!("Failed to evaluate HAVING clause: {}", e))
+        })?;
+
+        let evaluator = ExpressionEvaluator::new(group_by.to_vec());
+        let result = evaluator.evaluate(&rewritten, key_values).map_err(|e| {
+            StorageError::ReadError(format!("Failed to evaluate HAVING clause: {}", e))
+        })?;
+
+        Ok(matches!(result, Some(true)))
+    }
+
+    /// Recursively replaces every recognized aggregate call in `expr` with the
+    /// literal it folds to over `group_rows`, leaving everything else untouched.
+    fn rewrite_aggregates(
+        expr: &sqlparser::ast::Expr,
+        column_names: &[String],
+        group_rows: &[Row],
+    ) -> anyhow::Result<sqlparser::ast::Expr> {
+        use sqlparser::ast::Expr as SqlExpr;
+
+        match expr {
+            SqlExpr::Function(function) => {
+                match crate::sql::parser::Parser::convert_aggregate(function)? {
+                    Some((func, column)) => {
+                        let value =
+                            Self::fold_aggregate(func, column.as_deref(), column_names, group_rows)
+                                .map_err(|e| anyhow!(e.to_string()))?;
+                        Ok(Self::value_to_sql_literal(&value))
+                    }
+                    None => Ok(expr.clone()),
+                }
+            }
+            SqlExpr::BinaryOp { left, op, right } => Ok(SqlExpr::BinaryOp {
+                left: Box::new(Self::rewrite_aggregates(left, column_names, group_rows)?),
+                op: op.clone(),
+                right: Box::new(Self::rewrite_aggregates(right, column_names, group_rows)?),
+            }),
+            SqlExpr::UnaryOp { op, expr: inner } => Ok(SqlExpr::UnaryOp {
+                op: op.clone(),
+                expr: Box::new(Self::rewrite_aggregates(inner, column_names, group_rows)?),
+            }),
+            SqlExpr::Nested(inner) => Ok(SqlExpr::Nested(Box::new(Self::rewrite_aggregates(
+                inner,
+                column_names,
+                group_rows,
+            )?))),
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Converts a folded aggregate result back into a literal `sqlparser` expression
+    /// so it can be spliced into a rewritten HAVING predicate.
+    fn value_to_sql_literal(value: &Value) -> sqlparser::ast::Expr {
+        use sqlparser::ast::{Expr as SqlExpr, Value as SqlValue};
+
+        match value {
+            Value::Integer(n) => SqlExpr::Value(SqlValue::Number(n.to_string(), false)),
+            Value::Float(f) => SqlExpr::Value(SqlValue::Number(f.to_string(), false)),
+            Value::Text(s) => SqlExpr::Value(SqlValue::SingleQuotedString(s.clone())),
+            Value::Boolean(b) => SqlExpr::Value(SqlValue::Boolean(*b)),
+            Value::Json(_) | Value::Null => SqlExpr::Value(SqlValue::Null),
+            Value::Placeholder(_) => {
+                unreachable!("placeholders are bound before an aggregate is folded")
+            }
+        }
+    }
+}
+
+/// Running per-group fold for one aggregate call: `count`/`sum` feed `COUNT`,
+/// `SUM`, and `AVG` (which derives `sum / count`), while `min`/`max` track the
+/// smallest/largest non-NULL value seen so far.
+#[derive(Default)]
+struct Accumulator {
+    count: i64,
+    sum: f64,
+    sum_is_float: bool,
+    min: Option<Value>,
+    max: Option<Value>,
+}
+
+impl Accumulator {
+    /// `COUNT(*)` counts every row, including ones whose columns are NULL.
+    fn accumulate_star(&mut self) {
+        self.count += 1;
+    }
+
+    /// `COUNT(col)`/`SUM`/`AVG`/`MIN`/`MAX` all skip NULLs.
+    fn accumulate(&mut self, value: &Value) {
+        if matches!(value, Value::Null) {
+            return;
+        }
+
+        self.count += 1;
+        if let Value::Float(_) = value {
+            self.sum_is_float = true;
+        }
+        if let Ok(n) = Self::as_f64(value) {
+            self.sum += n;
+        }
+
+        if self.min.as_ref().map_or(true, |current| Self::less_than(value, current)) {
+            self.min = Some(value.clone());
+        }
+        if self.max.as_ref().map_or(true, |current| Self::less_than(current, value)) {
+            self.max = Some(value.clone());
+        }
+    }
+
+    fn as_f64(value: &Value) -> Result<f64> {
+        match value {
+            Value::Integer(n) => Ok(*n as f64),
+            Value::Float(f) => Ok(*f),
+            other => Err(StorageError::ReadError(format!(
+                "Expected a numeric value for aggregation, found {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn less_than(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Integer(x), Value::Integer(y)) => x < y,
+            (Value::Float(x), Value::Float(y)) => x < y,
+            (Value::Integer(x), Value::Float(y)) => (*x as f64) < *y,
+            (Value::Float(x), Value::Integer(y)) => *x < (*y as f64),
+            (Value::Text(x), Value::Text(y)) => x < y,
+            (Value::Boolean(x), Value::Boolean(y)) => !x && *y,
+            _ => false,
+        }
+    }
+
+    fn finish(&self, func: AggregateFunc) -> Value {
+        match func {
+            AggregateFunc::Count => Value::Integer(self.count),
+            AggregateFunc::Sum if self.count == 0 => Value::Null,
+            AggregateFunc::Sum if self.sum_is_float => Value::Float(self.sum),
+            AggregateFunc::Sum => Value::Integer(self.sum as i64),
+            AggregateFunc::Avg if self.count == 0 => Value::Null,
+            AggregateFunc::Avg => Value::Float(self.sum / self.count as f64),
+            AggregateFunc::Min => self.min.clone().unwrap_or(Value::Null),
+            AggregateFunc::Max => self.max.clone().unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl SubqueryRunner for Executor {
+    fn run_subquery(
+        &self,
+        query: &sqlparser::ast::Query,
+        outer_columns: &[String],
+        outer_row: &[Value],
+    ) -> anyhow::Result<Vec<Value>> {
+        let rows = self.materialize_subquery(query, outer_columns, outer_row)?;
+        rows.iter()
+            .map(|row| Self::scalar_projection(&row.projection, &row.column_names, &row.values))
+            .collect()
+    }
+
+    fn subquery_has_rows(
+        &self,
+        query: &sqlparser::ast::Query,
+        outer_columns: &[String],
+        outer_row: &[Value],
+    ) -> anyhow::Result<bool> {
+        Ok(!self
+            .materialize_subquery(query, outer_columns, outer_row)?
+            .is_empty())
+    }
+}
+
+/// One matching row from a correlated subquery, carrying enough of its own schema
+/// to resolve the first projected column afterward.
+struct SubqueryRow {
+    column_names: Vec<String>,
+    projection: Vec<sqlparser::ast::SelectItem>,
+    values: Vec<Value>,
+}
+
+impl Executor {
+    /// Runs a subquery's `SELECT ... FROM table WHERE ...` body as a full scan,
+    /// correlating it against the outer row by appending `outer_row`'s values after
+    /// the subquery's own columns in the evaluation namespace (so an inner
+    /// identifier shadows an outer one of the same name). Outer columns resolve by
+    /// bare name only; a qualified reference like `o.user_id` is read as a JSON
+    /// path into a column named `o`.
+    fn materialize_subquery(
+        &self,
+        query: &sqlparser::ast::Query,
+        outer_columns: &[String],
+        outer_row: &[Value],
+    ) -> anyhow::Result<Vec<SubqueryRow>> {
+        use sqlparser::ast::{SetExpr, TableFactor};
+
+        let select = match query.body.as_ref() {
+            SetExpr::Select(select) => select,
+            other => return Err(anyhow!("Unsupported subquery body: {:?}", other)),
+        };
+
+        let table = if let Some(table_with_joins) = select.from.first() {
+            if let TableFactor::Table { name, .. } = &table_with_joins.relation {
+                name.to_string()
+            } else {
+                return Err(anyhow!("Unsupported subquery FROM clause"));
+            }
+        } else {
+            return Err(anyhow!("Subquery has no FROM clause"));
+        };
+
+        let schema = self
+            .catalog
+            .get_table(&table)
+            .map_err(|e| anyhow!("Failed to look up subquery table {}: {}", table, e))?
+            .ok_or_else(|| anyhow!("Table {} not found", table))?;
+
+        let inner_column_names: Vec<String> =
+            schema.columns.iter().map(|c| c.name.clone()).collect();
+        let mut column_names = inner_column_names.clone();
+        column_names.extend(outer_columns.iter().cloned());
+
+        let evaluator = ExpressionEvaluator::with_runner(column_names.clone(), self);
+
+        let prefix = Self::table_data_prefix(&table);
+        let stored_rows = self
+            .storage
+            .scan_prefix(&prefix)
+            .map_err(|e| anyhow!("Failed to scan subquery table {}: {}", table, e))?;
+
+        let mut matches = Vec::new();
+        for (_, value) in &stored_rows {
+            let Ok(row) = serde_json::from_slice::<Row>(value) else {
+                continue;
+            };
+            let row = Self::pad_row(&schema, row);
+
+            let mut combined = row.values.clone();
+            combined.extend(outer_row.iter().cloned());
+
+            let is_match = match &select.selection {
+                Some(where_expr) => {
+                    matches!(evaluator.evaluate(where_expr, &combined), Ok(Some(true)))
+                }
+                None => true,
+            };
+
+            if is_match {
+                matches.push(SubqueryRow {
+                    column_names: inner_column_names.clone(),
+                    projection: select.projection.clone(),
+                    values: row.values,
+                });
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Evaluates the subquery's first projected column for one matching row.
+    /// A `SELECT *` (or any other non-expression) projection falls back to the
+    /// row's first stored column.
+    fn scalar_projection(
+        projection: &[sqlparser::ast::SelectItem],
+        column_names: &[String],
+        row_values: &[Value],
+    ) -> anyhow::Result<Value> {
+        use sqlparser::ast::SelectItem as SqlSelectItem;
+
+        let evaluator = ExpressionEvaluator::new(column_names.to_vec());
+        match projection.first() {
+            Some(SqlSelectItem::UnnamedExpr(expr)) | Some(SqlSelectItem::ExprWithAlias { expr, .. }) => {
+                evaluator.evaluate_scalar(expr, row_values)
+            }
+            _ => row_values
+                .first()
+                .cloned()
+                .ok_or_else(|| anyhow!("Subquery row has no columns to project")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExecutionResult {
+    Created {
+        table: String,
+    },
+    Inserted {
+        table: String,
         rows: usize,
     },
     Selected {
         columns: Vec<String>,
         rows: Vec<Row>,
     },
+    Updated {
+        table: String,
+        rows: usize,
+    },
     Deleted {
         table: String,
         rows: usize,
     },
+    Tables {
+        tables: Vec<String>,
+    },
+    Described {
+        table: String,
+        columns: Vec<crate::sql::types::Column>,
+    },
+    Dropped {
+        table: String,
+    },
+    Altered {
+        table: String,
+    },
+    IndexCreated {
+        name: String,
+        table: String,
+        column: String,
+    },
+    TransactionStarted,
+    TransactionCommitted,
+    TransactionRolledBack,
+    Explain(String),
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::sql::types::{Column, DataType};
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::types::{Column, DataType};
+
+    #[test]
+    fn test_end_to_end_execution() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "test_table".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+
+        let result = executor.execute(create).unwrap();
+        match result {
+            ExecutionResult::Created { table } => assert_eq!(table, "test_table"),
+            _ => panic!("Expected Created result"),
+        }
+
+        let insert = Statement::Insert {
+            table: "test_table".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![
+                vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                vec![Value::Integer(2), Value::Text("Bob".to_string())],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+
+        let result = executor.execute(insert).unwrap();
+        match result {
+            ExecutionResult::Inserted { rows, .. } => assert_eq!(rows, 2),
+            _ => panic!("Expected Inserted result"),
+        }
+
+        let select = Statement::Select {
+            table: "test_table".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        let result = executor.execute(select).unwrap();
+
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 2);
+            }
+            _ => panic!("Expected selected"),
+        }
+    }
+
+    #[test]
+    fn test_delete_with_where_clause() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        // Create table
+        let create = Statement::CreateTable {
+            name: "test_delete".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+
+        // Insert rows
+        let insert = Statement::Insert {
+            table: "test_delete".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![
+                vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                vec![Value::Integer(2), Value::Text("Bob".to_string())],
+                vec![Value::Integer(3), Value::Text("Charlie".to_string())],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+        executor.execute(insert).unwrap();
+
+        // Delete with WHERE clause
+        use sqlparser::dialect::GenericDialect;
+        use sqlparser::parser::Parser as SqlParser;
+        let dialect = GenericDialect {};
+        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 2").unwrap();
+        let where_expr = if let sqlparser::ast::Statement::Query(query) = &ast[0] {
+            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
+                select.selection.clone().map(Box::new)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let delete = Statement::Delete {
+            table: "test_delete".to_string(),
+            where_clause: where_expr,
+            returning: None,
+        };
+
+        let result = executor.execute(delete).unwrap();
+        match result {
+            ExecutionResult::Deleted { table, rows } => {
+                assert_eq!(table, "test_delete");
+                assert_eq!(rows, 1);
+            }
+            _ => panic!("Expected Deleted result"),
+        }
+
+        // Verify only 2 rows remain
+        let select = Statement::Select {
+            table: "test_delete".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        let result = executor.execute(select).unwrap();
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 2);
+            }
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_delete_with_where_clause_uses_index_scan() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "test_delete_indexed".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("name", DataType::Text),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor
+            .execute(Statement::CreateIndex {
+                name: "idx_id".to_string(),
+                table: "test_delete_indexed".to_string(),
+                column: "id".to_string(),
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "test_delete_indexed".to_string(),
+                columns: vec!["id".to_string(), "name".to_string()],
+                values: vec![
+                    vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                    vec![Value::Integer(2), Value::Text("Bob".to_string())],
+                    vec![Value::Integer(3), Value::Text("Charlie".to_string())],
+                ],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        use sqlparser::dialect::GenericDialect;
+        use sqlparser::parser::Parser as SqlParser;
+        let dialect = GenericDialect {};
+        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 2").unwrap();
+        let where_expr = if let sqlparser::ast::Statement::Query(query) = &ast[0] {
+            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
+                select.selection.clone().map(Box::new)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let result = executor
+            .execute(Statement::Delete {
+                table: "test_delete_indexed".to_string(),
+                where_clause: where_expr,
+                returning: None,
+            })
+            .unwrap();
+        match result {
+            ExecutionResult::Deleted { table, rows } => {
+                assert_eq!(table, "test_delete_indexed");
+                assert_eq!(rows, 1);
+            }
+            _ => panic!("Expected Deleted result"),
+        }
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "test_delete_indexed".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 2);
+                assert!(rows.iter().all(|row| row.values[0] != Value::Integer(2)));
+            }
+            _ => panic!("Expected Selected result"),
+        }
+    }
 
     #[test]
-    fn test_end_to_end_execution() {
+    fn test_update_with_where_clause() {
         let storage = StorageEngine::memory().unwrap();
         let executor = Executor::new(storage);
 
         let create = Statement::CreateTable {
-            name: "test_table".to_string(),
+            name: "test_update".to_string(),
             columns: vec![
-                Column {
-                    name: "id".to_string(),
-                    data_type: DataType::Integer,
-                },
-                Column {
-                    name: "name".to_string(),
-                    data_type: DataType::Text,
-                },
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
             ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
         };
+        executor.execute(create).unwrap();
 
-        let result = executor.execute(create).unwrap();
+        let insert = Statement::Insert {
+            table: "test_update".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![
+                vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                vec![Value::Integer(2), Value::Text("Bob".to_string())],
+                vec![Value::Integer(3), Value::Text("Charlie".to_string())],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+        executor.execute(insert).unwrap();
+
+        use sqlparser::dialect::GenericDialect;
+        use sqlparser::parser::Parser as SqlParser;
+        let dialect = GenericDialect {};
+        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 2").unwrap();
+        let where_expr = if let sqlparser::ast::Statement::Query(query) = &ast[0] {
+            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
+                select.selection.clone().map(Box::new)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let update = Statement::Update {
+            table: "test_update".to_string(),
+            assignments: vec![(
+                "name".to_string(),
+                sqlparser::ast::Expr::Value(sqlparser::ast::Value::SingleQuotedString(
+                    "Bobby".to_string(),
+                )),
+            )],
+            where_clause: where_expr,
+        };
+
+        let result = executor.execute(update).unwrap();
         match result {
-            ExecutionResult::Created { table } => assert_eq!(table, "test_table"),
-            _ => panic!("Expected Created result"),
+            ExecutionResult::Updated { table, rows } => {
+                assert_eq!(table, "test_update");
+                assert_eq!(rows, 1);
+            }
+            _ => panic!("Expected Updated result"),
+        }
+
+        let select = Statement::Select {
+            table: "test_update".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        let result = executor.execute(select).unwrap();
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 3);
+                let updated = rows
+                    .iter()
+                    .find(|r| r.values[0] == Value::Integer(2))
+                    .unwrap();
+                assert_eq!(updated.values[1], Value::Text("Bobby".to_string()));
+            }
+            _ => panic!("Expected Selected result"),
         }
+    }
+
+    #[test]
+    fn test_insert_and_delete_returning() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "test_returning".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
 
         let insert = Statement::Insert {
-            table: "test_table".to_string(),
+            table: "test_returning".to_string(),
             columns: vec!["id".to_string(), "name".to_string()],
             values: vec![
                 vec![Value::Integer(1), Value::Text("Alice".to_string())],
                 vec![Value::Integer(2), Value::Text("Bob".to_string())],
             ],
+            returning: Some(vec!["id".to_string()]),
+            on_conflict: OnConflict::Abort,
         };
 
-        let result = executor.execute(insert).unwrap();
+        match executor.execute(insert).unwrap() {
+            ExecutionResult::Selected { columns, rows } => {
+                assert_eq!(columns, vec!["id".to_string()]);
+                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[0].values, vec![Value::Integer(1)]);
+                assert_eq!(rows[1].values, vec![Value::Integer(2)]);
+            }
+            _ => panic!("Expected Selected result from INSERT ... RETURNING"),
+        }
+
+        use sqlparser::dialect::GenericDialect;
+        use sqlparser::parser::Parser as SqlParser;
+        let dialect = GenericDialect {};
+        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 1").unwrap();
+        let where_expr = if let sqlparser::ast::Statement::Query(query) = &ast[0] {
+            if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
+                select.selection.clone().map(Box::new)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let delete = Statement::Delete {
+            table: "test_returning".to_string(),
+            where_clause: where_expr,
+            returning: Some(vec!["name".to_string()]),
+        };
+
+        match executor.execute(delete).unwrap() {
+            ExecutionResult::Selected { columns, rows } => {
+                assert_eq!(columns, vec!["name".to_string()]);
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].values, vec![Value::Text("Alice".to_string())]);
+            }
+            _ => panic!("Expected Selected result from DELETE ... RETURNING"),
+        }
+    }
+
+    #[test]
+    fn test_delete_all_rows() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        // Create table
+        let create = Statement::CreateTable {
+            name: "test_delete_all".to_string(),
+            columns: vec![Column::new("id", DataType::Integer)],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+
+        // Insert rows
+        let insert = Statement::Insert {
+            table: "test_delete_all".to_string(),
+            columns: vec!["id".to_string()],
+            values: vec![
+                vec![Value::Integer(1)],
+                vec![Value::Integer(2)],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+        executor.execute(insert).unwrap();
+
+        // Delete all (no WHERE clause)
+        let delete = Statement::Delete {
+            table: "test_delete_all".to_string(),
+            where_clause: None,
+            returning: None,
+        };
+
+        let result = executor.execute(delete).unwrap();
         match result {
-            ExecutionResult::Inserted { rows, .. } => assert_eq!(rows, 2),
-            _ => panic!("Expected Inserted result"),
+            ExecutionResult::Deleted { rows, .. } => {
+                assert_eq!(rows, 2);
+            }
+            _ => panic!("Expected Deleted result"),
+        }
+
+        // Verify no rows remain
+        let select = Statement::Select {
+            table: "test_delete_all".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        let result = executor.execute(select).unwrap();
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 0);
+            }
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_select_uses_index_scan_for_indexed_equality() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "indexed_users".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("name", DataType::Text),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+        executor.catalog.create_index("indexed_users", "id").unwrap();
+
+        let insert = Statement::Insert {
+            table: "indexed_users".to_string(),
+            columns: vec!["id".to_string(), "name".to_string()],
+            values: vec![
+                vec![Value::Integer(1), Value::Text("Alice".to_string())],
+                vec![Value::Integer(2), Value::Text("Bob".to_string())],
+                vec![Value::Integer(3), Value::Text("Charlie".to_string())],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+        executor.execute(insert).unwrap();
+
+        use sqlparser::ast::{BinaryOperator, Expr, Ident, Value as SqlValue};
+        let where_clause = Some(Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("id"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(SqlValue::Number("2".to_string(), false))),
+        }));
+
+        let select = Statement::Select {
+            table: "indexed_users".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        let result = executor.execute(select).unwrap();
+
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].values[0], Value::Integer(2));
+            }
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_select_group_by_with_having() {
+        use crate::sql::types::AggregateFunc;
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "employees".to_string(),
+            columns: vec![
+                Column::new("department", DataType::Text),
+                Column::new("salary", DataType::Integer),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+
+        let insert = Statement::Insert {
+            table: "employees".to_string(),
+            columns: vec!["department".to_string(), "salary".to_string()],
+            values: vec![
+                vec![Value::Text("eng".to_string()), Value::Integer(100)],
+                vec![Value::Text("eng".to_string()), Value::Integer(200)],
+                vec![Value::Text("sales".to_string()), Value::Integer(50)],
+            ],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        };
+        executor.execute(insert).unwrap();
+
+        use sqlparser::ast::{BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, Value as SqlValue};
+
+        let count_star = Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("COUNT")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
+            over: None,
+            distinct: false,
+            special: false,
+            order_by: vec![],
+        });
+
+        let having = Some(Box::new(Expr::BinaryOp {
+            left: Box::new(count_star.clone()),
+            op: BinaryOperator::Gt,
+            right: Box::new(Expr::Value(SqlValue::Number("1".to_string(), false))),
+        }));
+
+        let select = Statement::Select {
+            table: "employees".to_string(),
+            joins: vec![],
+            projection: vec![
+                SelectItem::Column {
+                    name: "department".to_string(),
+                    alias: None,
+                },
+                SelectItem::Aggregate {
+                    func: AggregateFunc::Count,
+                    column: None,
+                    alias: Some("n".to_string()),
+                },
+            ],
+            where_clause: None,
+            group_by: vec!["department".to_string()],
+            having,
+            order_by: None,
+            limit: None,
+        };
+
+        let result = executor.execute(select).unwrap();
+        match result {
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].values[0], Value::Text("eng".to_string()));
+                assert_eq!(rows[0].values[1], Value::Integer(2));
+            }
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_transaction_commits_all_statements_atomically() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "accounts".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("balance", DataType::Integer),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+
+        executor
+            .transaction(|tx| {
+                tx.execute(Statement::Insert {
+                    table: "accounts".to_string(),
+                    columns: vec!["id".to_string(), "balance".to_string()],
+                    values: vec![vec![Value::Integer(1), Value::Integer(100)]],
+                    returning: None,
+                    on_conflict: OnConflict::Abort,
+                })?;
+                tx.execute(Statement::Insert {
+                    table: "accounts".to_string(),
+                    columns: vec!["id".to_string(), "balance".to_string()],
+                    values: vec![vec![Value::Integer(2), Value::Integer(200)]],
+                    returning: None,
+                    on_conflict: OnConflict::Abort,
+                })?;
+                Ok(())
+            })
+            .unwrap();
+
+        let select = Statement::Select {
+            table: "accounts".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+        match executor.execute(select).unwrap() {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 2),
+            _ => panic!("Expected Selected result"),
         }
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_every_statement_on_error() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let create = Statement::CreateTable {
+            name: "accounts".to_string(),
+            columns: vec![
+                Column::new("id", DataType::Integer),
+                Column::new("balance", DataType::Integer),
+            ],
+            unique_groups: vec![],
+            foreign_keys: vec![],
+        };
+        executor.execute(create).unwrap();
+
+        let result = executor.transaction(|tx| {
+            tx.execute(Statement::Insert {
+                table: "accounts".to_string(),
+                columns: vec!["id".to_string(), "balance".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(100)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })?;
+            tx.execute(Statement::Insert {
+                table: "missing_table".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+        });
+        assert!(result.is_err());
 
         let select = Statement::Select {
-            table: "test_table".to_string(),
-            columns: vec!["*".to_string()],
+            table: "accounts".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
             where_clause: None,
+            group_by: vec![],
+            having: None,
             order_by: None,
             limit: None,
         };
-        let result = executor.execute(select).unwrap();
-
-        match result {
-            ExecutionResult::Selected { rows, .. } => {
-                assert_eq!(rows.len(), 2);
-            }
-            _ => panic!("Expected selected"),
+        match executor.execute(select).unwrap() {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 0),
+            _ => panic!("Expected Selected result"),
         }
     }
 
     #[test]
-    fn test_delete_with_where_clause() {
+    fn test_begin_commit_rollback_statements() {
         let storage = StorageEngine::memory().unwrap();
         let executor = Executor::new(storage);
 
-        // Create table
         let create = Statement::CreateTable {
-            name: "test_delete".to_string(),
-            columns: vec![
-                Column {
-                    name: "id".to_string(),
-                    data_type: DataType::Integer,
-                },
-                Column {
-                    name: "name".to_string(),
-                    data_type: DataType::Text,
-                },
-            ],
+            name: "widgets".to_string(),
+            columns: vec![Column::new("id", DataType::Integer)],
+            unique_groups: vec![],
+            foreign_keys: vec![],
         };
         executor.execute(create).unwrap();
 
-        // Insert rows
-        let insert = Statement::Insert {
-            table: "test_delete".to_string(),
-            columns: vec!["id".to_string(), "name".to_string()],
-            values: vec![
-                vec![Value::Integer(1), Value::Text("Alice".to_string())],
-                vec![Value::Integer(2), Value::Text("Bob".to_string())],
-                vec![Value::Integer(3), Value::Text("Charlie".to_string())],
-            ],
+        assert!(matches!(
+            executor.execute(Statement::Begin).unwrap(),
+            ExecutionResult::TransactionStarted
+        ));
+        executor
+            .execute(Statement::Insert {
+                table: "widgets".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        assert!(matches!(
+            executor.execute(Statement::Rollback).unwrap(),
+            ExecutionResult::TransactionRolledBack
+        ));
+
+        let select = Statement::Select {
+            table: "widgets".to_string(),
+            joins: vec![],
+            projection: vec![SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
         };
-        executor.execute(insert).unwrap();
+        match executor.execute(select.clone()).unwrap() {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 0),
+            _ => panic!("Expected Selected result"),
+        }
+
+        executor.execute(Statement::Begin).unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "widgets".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        assert!(matches!(
+            executor.execute(Statement::Commit).unwrap(),
+            ExecutionResult::TransactionCommitted
+        ));
+
+        match executor.execute(select).unwrap() {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 1),
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_explain_reports_index_scan_for_indexed_predicate() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("name", DataType::Text),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor
+            .execute(Statement::CreateIndex {
+                name: "idx_id".to_string(),
+                table: "users".to_string(),
+                column: "id".to_string(),
+            })
+            .unwrap();
 
-        // Delete with WHERE clause
         use sqlparser::dialect::GenericDialect;
         use sqlparser::parser::Parser as SqlParser;
         let dialect = GenericDialect {};
-        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 2").unwrap();
+        let ast = SqlParser::parse_sql(&dialect, "SELECT * FROM t WHERE id = 1").unwrap();
         let where_expr = if let sqlparser::ast::Statement::Query(query) = &ast[0] {
             if let sqlparser::ast::SetExpr::Select(select) = &*query.body {
                 select.selection.clone().map(Box::new)
@@ -412,93 +3288,943 @@ mod tests {
             None
         };
 
-        let delete = Statement::Delete {
-            table: "test_delete".to_string(),
-            where_clause: where_expr,
+        let explain = Statement::Explain {
+            query: Box::new(Statement::Select {
+                table: "users".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: where_expr,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            }),
         };
 
-        let result = executor.execute(delete).unwrap();
+        match executor.execute(explain).unwrap() {
+            ExecutionResult::Explain(description) => {
+                assert!(description.contains("BitmapScan"));
+            }
+            _ => panic!("Expected Explain result"),
+        }
+    }
+
+    #[test]
+    fn test_watcher_receives_events_for_its_table_only() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "orders".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        let orders_watcher = executor.watch(Some("orders".to_string()));
+
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        assert!(orders_watcher.try_recv().is_err());
+
+        executor
+            .execute(Statement::Insert {
+                table: "orders".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(7)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let event = orders_watcher.try_recv().unwrap();
+        assert_eq!(event.table, "orders");
+        assert_eq!(event.op, ChangeOp::Insert);
+        assert_eq!(event.row.values, vec![Value::Integer(7)]);
+        assert!(event.old_row.is_none());
+    }
+
+    #[test]
+    fn test_watcher_sees_no_events_from_a_rolled_back_transaction() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "accounts".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        let watcher = executor.watch(None);
+
+        let result = executor.transaction(|tx| -> Result<()> {
+            tx.execute(Statement::Insert {
+                table: "accounts".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })?;
+            Err(StorageError::WriteError("force rollback".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(watcher.try_recv().is_err());
+
+        executor
+            .transaction(|tx| {
+                tx.execute(Statement::Insert {
+                    table: "accounts".to_string(),
+                    columns: vec!["id".to_string()],
+                    values: vec![vec![Value::Integer(2)]],
+                    returning: None,
+                    on_conflict: OnConflict::Abort,
+                })
+            })
+            .unwrap();
+
+        let event = watcher.try_recv().unwrap();
+        assert_eq!(event.op, ChangeOp::Insert);
+        assert_eq!(event.row.values, vec![Value::Integer(2)]);
+    }
+
+    #[test]
+    fn test_foreign_key_cascade_on_delete() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "customers".to_string(),
+                columns: vec![id_col],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "orders".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("customer_id", DataType::Integer),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["customer_id".to_string()],
+                    ref_table: "customers".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        // A row whose foreign key doesn't match any customer is rejected.
+        let orphan = executor.execute(Statement::Insert {
+            table: "orders".to_string(),
+            columns: vec!["id".to_string(), "customer_id".to_string()],
+            values: vec![vec![Value::Integer(1), Value::Integer(99)]],
+            returning: None,
+            on_conflict: OnConflict::Abort,
+        });
+        assert!(orphan.is_err());
+
+        executor
+            .execute(Statement::Insert {
+                table: "orders".to_string(),
+                columns: vec!["id".to_string(), "customer_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Delete {
+                table: "customers".to_string(),
+                where_clause: None,
+                returning: None,
+            })
+            .unwrap();
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "orders".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
+
         match result {
-            ExecutionResult::Deleted { table, rows } => {
-                assert_eq!(table, "test_delete");
-                assert_eq!(rows, 1);
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 0),
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_foreign_key_cascade_on_delete_emits_change_events_for_child_rows() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "customers".to_string(),
+                columns: vec![id_col],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "orders".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("customer_id", DataType::Integer),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["customer_id".to_string()],
+                    ref_table: "customers".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "orders".to_string(),
+                columns: vec!["id".to_string(), "customer_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let orders_watcher = executor.watch(Some("orders".to_string()));
+
+        executor
+            .execute(Statement::Delete {
+                table: "customers".to_string(),
+                where_clause: None,
+                returning: None,
+            })
+            .unwrap();
+
+        let event = orders_watcher.try_recv().unwrap();
+        assert_eq!(event.table, "orders");
+        assert_eq!(event.op, ChangeOp::Delete);
+        assert_eq!(
+            event.row.values,
+            vec![Value::Integer(1), Value::Integer(1)]
+        );
+        assert!(orders_watcher.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_update_rejects_primary_key_change_when_referenced_by_foreign_key() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "customers".to_string(),
+                columns: vec![id_col],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "orders".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("customer_id", DataType::Integer),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["customer_id".to_string()],
+                    ref_table: "customers".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let result = executor.execute(Statement::Update {
+            table: "customers".to_string(),
+            assignments: vec![(
+                "id".to_string(),
+                sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number("2".to_string(), false)),
+            )],
+            where_clause: None,
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_allows_primary_key_change_when_not_referenced() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "customers".to_string(),
+                columns: vec![id_col],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let result = executor.execute(Statement::Update {
+            table: "customers".to_string(),
+            assignments: vec![(
+                "id".to_string(),
+                sqlparser::ast::Expr::Value(sqlparser::ast::Value::Number("2".to_string(), false)),
+            )],
+            where_clause: None,
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_foreign_key_cascade_recurses_through_multiple_levels() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut customer_id = Column::new("id", DataType::Integer);
+        customer_id.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "customers".to_string(),
+                columns: vec![customer_id],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        let mut order_id = Column::new("id", DataType::Integer);
+        order_id.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "orders".to_string(),
+                columns: vec![order_id, Column::new("customer_id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["customer_id".to_string()],
+                    ref_table: "customers".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "line_items".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("order_id", DataType::Integer),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["order_id".to_string()],
+                    ref_table: "orders".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "customers".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "orders".to_string(),
+                columns: vec!["id".to_string(), "customer_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "line_items".to_string(),
+                columns: vec!["id".to_string(), "order_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Delete {
+                table: "customers".to_string(),
+                where_clause: None,
+                returning: None,
+            })
+            .unwrap();
+
+        for table in ["orders", "line_items"] {
+            let result = executor
+                .execute(Statement::Select {
+                    table: table.to_string(),
+                    joins: vec![],
+                    projection: vec![SelectItem::Wildcard],
+                    where_clause: None,
+                    group_by: vec![],
+                    having: None,
+                    order_by: None,
+                    limit: None,
+                })
+                .unwrap();
+            match result {
+                ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 0, "{}", table),
+                _ => panic!("Expected Selected result"),
             }
-            _ => panic!("Expected Deleted result"),
         }
+    }
 
-        // Verify only 2 rows remain
-        let select = Statement::Select {
-            table: "test_delete".to_string(),
-            columns: vec!["*".to_string()],
+    #[test]
+    fn test_foreign_key_cascade_on_self_referencing_table_does_not_loop_forever() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "categories".to_string(),
+                columns: vec![id_col, Column::new("parent_id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["parent_id".to_string()],
+                    ref_table: "categories".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "categories".to_string(),
+                columns: vec!["id".to_string(), "parent_id".to_string()],
+                values: vec![
+                    vec![Value::Integer(1), Value::Integer(1)],
+                    vec![Value::Integer(2), Value::Integer(1)],
+                ],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let result = executor.execute(Statement::Delete {
+            table: "categories".to_string(),
             where_clause: None,
-            order_by: None,
-            limit: None,
-        };
-        let result = executor.execute(select).unwrap();
+            returning: None,
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_foreign_key_cascade_on_mutually_referencing_tables_does_not_loop_forever() {
+        use crate::sql::types::{ForeignKey, ReferentialAction};
+
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        let mut deps_id = Column::new("id", DataType::Integer);
+        deps_id.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "deps".to_string(),
+                columns: vec![deps_id, Column::new("head_id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["head_id".to_string()],
+                    ref_table: "heads".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        let mut heads_id = Column::new("id", DataType::Integer);
+        heads_id.primary_key = true;
+        executor
+            .execute(Statement::CreateTable {
+                name: "heads".to_string(),
+                columns: vec![heads_id, Column::new("dep_id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![ForeignKey {
+                    columns: vec!["dep_id".to_string()],
+                    ref_table: "deps".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "deps".to_string(),
+                columns: vec!["id".to_string(), "head_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "heads".to_string(),
+                columns: vec!["id".to_string(), "dep_id".to_string()],
+                values: vec![vec![Value::Integer(1), Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        let result = executor.execute(Statement::Delete {
+            table: "deps".to_string(),
+            where_clause: None,
+            returning: None,
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_alter_table_add_column_pads_existing_rows() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::AlterTable {
+                name: "users".to_string(),
+                operations: vec![AlterTableOperation::AddColumn {
+                    column: Column::new("nickname", DataType::Text),
+                }],
+            })
+            .unwrap();
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "users".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
+
         match result {
             ExecutionResult::Selected { rows, .. } => {
-                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[0].values, vec![Value::Integer(1), Value::Null]);
             }
             _ => panic!("Expected Selected result"),
         }
+
+        // The new column is usable for further inserts right away.
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec!["id".to_string(), "nickname".to_string()],
+                values: vec![vec![Value::Integer(2), Value::Text("Bo".to_string())]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
     }
 
     #[test]
-    fn test_delete_all_rows() {
+    fn test_alter_table_drop_column_splices_stored_rows() {
         let storage = StorageEngine::memory().unwrap();
         let executor = Executor::new(storage);
 
-        // Create table
-        let create = Statement::CreateTable {
-            name: "test_delete_all".to_string(),
-            columns: vec![
-                Column {
-                    name: "id".to_string(),
-                    data_type: DataType::Integer,
-                },
-            ],
-        };
-        executor.execute(create).unwrap();
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("nickname", DataType::Text),
+                    Column::new("age", DataType::Integer),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
 
-        // Insert rows
-        let insert = Statement::Insert {
-            table: "test_delete_all".to_string(),
-            columns: vec!["id".to_string()],
-            values: vec![
-                vec![Value::Integer(1)],
-                vec![Value::Integer(2)],
-            ],
-        };
-        executor.execute(insert).unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec![],
+                values: vec![vec![
+                    Value::Integer(1),
+                    Value::Text("Al".to_string()),
+                    Value::Integer(30),
+                ]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
 
-        // Delete all (no WHERE clause)
-        let delete = Statement::Delete {
-            table: "test_delete_all".to_string(),
-            where_clause: None,
-        };
+        executor
+            .execute(Statement::AlterTable {
+                name: "users".to_string(),
+                operations: vec![AlterTableOperation::DropColumn {
+                    name: "nickname".to_string(),
+                }],
+            })
+            .unwrap();
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "users".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
 
-        let result = executor.execute(delete).unwrap();
         match result {
-            ExecutionResult::Deleted { rows, .. } => {
-                assert_eq!(rows, 2);
+            ExecutionResult::Selected { rows, .. } => {
+                assert_eq!(rows[0].values, vec![Value::Integer(1), Value::Integer(30)]);
             }
-            _ => panic!("Expected Deleted result"),
+            _ => panic!("Expected Selected result"),
         }
+    }
+
+    #[test]
+    fn test_alter_table_rename_table_preserves_data_and_indexes() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor.catalog.create_index("users", "id").unwrap();
+
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec!["id".to_string()],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::AlterTable {
+                name: "users".to_string(),
+                operations: vec![AlterTableOperation::RenameTable {
+                    new_name: "people".to_string(),
+                }],
+            })
+            .unwrap();
+
+        assert!(executor
+            .execute(Statement::Select {
+                table: "users".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .is_err());
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "people".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
 
-        // Verify no rows remain
-        let select = Statement::Select {
-            table: "test_delete_all".to_string(),
-            columns: vec!["*".to_string()],
-            where_clause: None,
-            order_by: None,
-            limit: None,
-        };
-        let result = executor.execute(select).unwrap();
         match result {
             ExecutionResult::Selected { rows, .. } => {
-                assert_eq!(rows.len(), 0);
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].values[0], Value::Integer(1));
             }
             _ => panic!("Expected Selected result"),
         }
+
+        use sqlparser::ast::{BinaryOperator, Expr, Ident, Value as SqlValue};
+        let where_clause = Some(Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("id"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(SqlValue::Number("1".to_string(), false))),
+        }));
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "people".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
+
+        match result {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 1),
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_create_table_as_select_infers_schema_and_copies_rows() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("name", DataType::Text),
+                ],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec![],
+                values: vec![
+                    vec![Value::Integer(1), Value::Text("Ada".to_string())],
+                    vec![Value::Integer(2), Value::Text("Bob".to_string())],
+                ],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTableAs {
+                name: "names".to_string(),
+                query: Box::new(Statement::Select {
+                    table: "users".to_string(),
+                    joins: vec![],
+                    projection: vec![SelectItem::Column {
+                        name: "name".to_string(),
+                        alias: None,
+                    }],
+                    where_clause: None,
+                    group_by: vec![],
+                    having: None,
+                    order_by: None,
+                    limit: None,
+                }),
+                temporary: false,
+            })
+            .unwrap();
+
+        let schema = executor.catalog.get_table("names").unwrap().unwrap();
+        assert_eq!(schema.columns.len(), 1);
+        assert_eq!(schema.columns[0].name, "name");
+        assert_eq!(schema.columns[0].data_type, DataType::Text);
+
+        let result = executor
+            .execute(Statement::Select {
+                table: "names".to_string(),
+                joins: vec![],
+                projection: vec![SelectItem::Wildcard],
+                where_clause: None,
+                group_by: vec![],
+                having: None,
+                order_by: None,
+                limit: None,
+            })
+            .unwrap();
+
+        match result {
+            ExecutionResult::Selected { rows, .. } => assert_eq!(rows.len(), 2),
+            _ => panic!("Expected Selected result"),
+        }
+    }
+
+    #[test]
+    fn test_create_temporary_table_as_select_hidden_from_list_tables() {
+        let storage = StorageEngine::memory().unwrap();
+        let executor = Executor::new(storage);
+
+        executor
+            .execute(Statement::CreateTable {
+                name: "users".to_string(),
+                columns: vec![Column::new("id", DataType::Integer)],
+                unique_groups: vec![],
+                foreign_keys: vec![],
+            })
+            .unwrap();
+        executor
+            .execute(Statement::Insert {
+                table: "users".to_string(),
+                columns: vec![],
+                values: vec![vec![Value::Integer(1)]],
+                returning: None,
+                on_conflict: OnConflict::Abort,
+            })
+            .unwrap();
+
+        executor
+            .execute(Statement::CreateTableAs {
+                name: "staging".to_string(),
+                query: Box::new(Statement::Select {
+                    table: "users".to_string(),
+                    joins: vec![],
+                    projection: vec![SelectItem::Wildcard],
+                    where_clause: None,
+                    group_by: vec![],
+                    having: None,
+                    order_by: None,
+                    limit: None,
+                }),
+                temporary: true,
+            })
+            .unwrap();
+
+        assert!(!executor
+            .catalog
+            .list_tables()
+            .unwrap()
+            .contains(&"staging".to_string()));
+        assert!(executor.catalog.get_table("staging").unwrap().is_some());
+
+        executor.catalog.drop_temp_tables().unwrap();
+        assert!(executor.catalog.get_table("staging").unwrap().is_none());
     }
 }
@@ -0,0 +1,145 @@
+use crate::catalog::Catalog;
+use crate::sql::types::{DataType, SelectItem};
+use crate::storage::{Result, StorageError};
+
+/// A projection column resolved against the catalog: which table it belongs
+/// to, its position in that table's schema, and its declared type — computed
+/// once so the planner and executor never need to re-look up the schema or
+/// re-parse a type name out of `{:?}` output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundColumn {
+    pub table: String,
+    pub name: String,
+    pub ordinal: usize,
+    pub data_type: DataType,
+}
+
+/// Resolves names against the catalog before the planner runs, so an unknown
+/// table or column is reported once, up front, instead of surfacing later as
+/// a silently empty result.
+pub struct Binder;
+
+impl Binder {
+    /// Resolves `table`'s `SELECT` projection, expanding `SelectItem::Wildcard`
+    /// into the table's concrete column list in schema order. Computed and
+    /// aggregate projections have no catalog-backed column to resolve, so
+    /// they are passed through rather than rejected.
+    pub fn bind_select_columns(
+        catalog: &Catalog,
+        table: &str,
+        projection: &[SelectItem],
+    ) -> Result<Vec<BoundColumn>> {
+        let schema = catalog
+            .get_table(table)?
+            .ok_or_else(|| StorageError::ReadError(format!("Table {} not found", table)))?;
+
+        let resolve = |name: &str| -> Result<BoundColumn> {
+            schema
+                .columns
+                .iter()
+                .position(|c| c.name == name)
+                .map(|ordinal| BoundColumn {
+                    table: table.to_string(),
+                    name: name.to_string(),
+                    ordinal,
+                    data_type: schema.columns[ordinal].data_type.clone(),
+                })
+                .ok_or_else(|| {
+                    StorageError::WriteError(format!(
+                        "Column {} not found on table {}",
+                        name, table
+                    ))
+                })
+        };
+
+        let mut bound = Vec::new();
+        for item in projection {
+            match item {
+                SelectItem::Wildcard => {
+                    for (ordinal, column) in schema.columns.iter().enumerate() {
+                        bound.push(BoundColumn {
+                            table: table.to_string(),
+                            name: column.name.clone(),
+                            ordinal,
+                            data_type: column.data_type.clone(),
+                        });
+                    }
+                }
+                SelectItem::Column { name, .. } => bound.push(resolve(name)?),
+                SelectItem::Expr { .. } | SelectItem::Aggregate { .. } => {}
+            }
+        }
+        Ok(bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Catalog;
+    use crate::sql::types::Column;
+    use crate::storage::StorageEngine;
+
+    fn setup() -> Catalog {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+        catalog
+            .create_table(
+                "users",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("name", DataType::Text),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+        catalog
+    }
+
+    #[test]
+    fn test_wildcard_expands_to_schema_columns_in_order() {
+        let catalog = setup();
+        let bound =
+            Binder::bind_select_columns(&catalog, "users", &[SelectItem::Wildcard]).unwrap();
+        assert_eq!(bound.len(), 2);
+        assert_eq!(bound[0].name, "id");
+        assert_eq!(bound[0].ordinal, 0);
+        assert_eq!(bound[0].data_type, DataType::Integer);
+        assert_eq!(bound[1].name, "name");
+        assert_eq!(bound[1].ordinal, 1);
+        assert_eq!(bound[1].data_type, DataType::Text);
+    }
+
+    #[test]
+    fn test_named_column_resolves_ordinal_and_type() {
+        let catalog = setup();
+        let projection = vec![SelectItem::Column {
+            name: "name".to_string(),
+            alias: None,
+        }];
+        let bound = Binder::bind_select_columns(&catalog, "users", &projection).unwrap();
+        assert_eq!(bound.len(), 1);
+        assert_eq!(bound[0].ordinal, 1);
+        assert_eq!(bound[0].data_type, DataType::Text);
+    }
+
+    #[test]
+    fn test_unknown_table_is_an_error() {
+        let catalog = setup();
+        let err =
+            Binder::bind_select_columns(&catalog, "ghosts", &[SelectItem::Wildcard]).unwrap_err();
+        assert!(err.to_string().contains("ghosts"));
+    }
+
+    #[test]
+    fn test_unknown_column_is_an_error() {
+        let catalog = setup();
+        let projection = vec![SelectItem::Column {
+            name: "email".to_string(),
+            alias: None,
+        }];
+        let err = Binder::bind_select_columns(&catalog, "users", &projection).unwrap_err();
+        assert!(err.to_string().contains("email"));
+    }
+}
@@ -1,7 +1,14 @@
+pub mod binder;
 pub mod parser;
 pub mod planner;
+pub mod prepared;
 pub mod types;
 
+pub use binder::{BoundColumn, Binder};
 pub use parser::Parser;
 pub use planner::Planner;
-pub use types::{DataType, SelectItem, Statement, Value};
+pub use prepared::{PreparedStatement, StatementCache};
+pub use types::{
+    AggregateFunc, AlterTableOperation, Column, CompareOp, DataType, ForeignKey, JoinClause,
+    JoinKind, Predicate, ReferentialAction, SelectItem, Statement, Value,
+};
@@ -1,6 +1,13 @@
-use super::types::{Column, DataType, SelectItem, Statement, Value};
+use super::types::{
+    AggregateFunc, AlterTableOperation, Column, DataType, ForeignKey, JoinClause, JoinKind,
+    OnConflict, ReferentialAction, SelectItem, Statement, Value,
+};
 use anyhow::{anyhow, Result};
-use sqlparser::ast::{self, ColumnDef, DataType as SqlDataType, Expr, Statement as SqlStatement};
+use sqlparser::ast::{
+    self, BinaryOperator, ColumnDef, ColumnOption, DataType as SqlDataType, Expr, Ident,
+    ReferentialAction as SqlReferentialAction, SqliteOnConflict, Statement as SqlStatement,
+    TableConstraint,
+};
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser as SqlParser;
 
@@ -13,6 +20,17 @@ impl Parser {
             return Err(anyhow!("No statements found"));
         }
 
+        let mut head = trimmed.splitn(2, char::is_whitespace);
+        if head.next().unwrap_or("").eq_ignore_ascii_case("explain") {
+            let rest = head.next().unwrap_or("").trim();
+            if rest.is_empty() {
+                return Err(anyhow!("Expected a statement after EXPLAIN"));
+            }
+            return Ok(Statement::Explain {
+                query: Box::new(Self::parse(rest)?),
+            });
+        }
+
         if let Some(statement) = Self::parse_management_statement(trimmed)? {
             return Ok(statement);
         }
@@ -30,33 +48,63 @@ impl Parser {
 
     fn convert_statement(stmt: &SqlStatement) -> Result<Statement> {
         match stmt {
-            SqlStatement::CreateTable { name, columns, .. } => {
+            SqlStatement::CreateTable {
+                name,
+                query: Some(query),
+                temporary,
+                ..
+            } => {
+                let inner = Self::convert_query(query)?;
+                Ok(Statement::CreateTableAs {
+                    name: name.to_string(),
+                    query: Box::new(inner),
+                    temporary: *temporary,
+                })
+            }
+            SqlStatement::CreateTable {
+                name,
+                columns,
+                constraints,
+                ..
+            } => {
                 let table_name = name.to_string();
-                let cols = columns
+                let mut cols = columns
                     .iter()
                     .map(Self::convert_column)
                     .collect::<Result<Vec<_>>>()?;
+
+                let mut unique_groups = Vec::new();
+                let mut foreign_keys = Vec::new();
+                for constraint in constraints {
+                    Self::apply_table_constraint(constraint, &mut cols, &mut unique_groups, &mut foreign_keys)?;
+                }
+
                 Ok(Statement::CreateTable {
                     name: table_name,
                     columns: cols,
+                    unique_groups,
+                    foreign_keys,
                 })
             }
             SqlStatement::Insert {
                 table_name,
                 columns,
                 source,
+                returning,
+                or,
                 ..
             } => {
                 let table = table_name.to_string();
                 let col_names = columns.iter().map(|c| c.to_string()).collect();
 
+                let mut next_placeholder = 0usize;
                 let values = if let ast::SetExpr::Values(values) = &*source.body {
                     values
                         .rows
                         .iter()
                         .map(|row| {
                             row.iter()
-                                .map(Self::convert_expr)
+                                .map(|expr| Self::convert_expr(expr, &mut next_placeholder))
                                 .collect::<Result<Vec<_>>>()
                         })
                         .collect::<Result<Vec<_>>>()?
@@ -64,10 +112,17 @@ impl Parser {
                     return Err(anyhow!("Unsupported INSERT format"));
                 };
 
+                let on_conflict = match or {
+                    Some(SqliteOnConflict::Replace) => OnConflict::Replace,
+                    _ => OnConflict::Abort,
+                };
+
                 Ok(Statement::Insert {
                     table,
                     columns: col_names,
                     values,
+                    returning: Self::convert_returning(returning)?,
+                    on_conflict,
                 })
             }
             SqlStatement::Update {
@@ -78,6 +133,11 @@ impl Parser {
             } => {
                 let table_name = table.to_string();
 
+                // One counter numbers bare `?` tokens across the whole
+                // statement, so `SET x = ? WHERE y = ?` sees them as
+                // placeholders 1 and 2 rather than each clause restarting
+                // at 1 on its own.
+                let mut next_placeholder = 0usize;
                 let assignment_pairs = assignments
                     .iter()
                     .map(|assign| {
@@ -87,12 +147,24 @@ impl Parser {
                             .map(|i| i.value.clone())
                             .collect::<Vec<_>>()
                             .join(".");
-                        let value = Self::convert_expr(&assign.value)?;
+                        // Kept as a raw `Expr` rather than lowered via
+                        // `convert_expr`, so `SET price = price * 2` (a
+                        // column reference, arithmetic, a function call...)
+                        // parses instead of hitting `convert_expr`'s
+                        // literal-only catch-all.
+                        let mut value = assign.value.clone();
+                        Self::number_placeholders(&mut value, &mut next_placeholder);
                         Ok((col_name, value))
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                let where_clause = selection.as_ref().map(|expr| Box::new(expr.clone()));
+                let where_clause = selection
+                    .as_ref()
+                    .map(|expr| {
+                        let mut expr = expr.clone();
+                        Self::number_placeholders(&mut expr, &mut next_placeholder);
+                        Box::new(expr)
+                    });
 
                 Ok(Statement::Update {
                     table: table_name,
@@ -101,7 +173,10 @@ impl Parser {
                 })
             }
             SqlStatement::Delete {
-                from, selection, ..
+                from,
+                selection,
+                returning,
+                ..
             } => {
                 let table = if let Some(from_clause) = from.first() {
                     from_clause.relation.to_string()
@@ -109,95 +184,173 @@ impl Parser {
                     return Err(anyhow!("DELETE requires a table name"));
                 };
 
-                let where_clause = selection.as_ref().map(|expr| Box::new(expr.clone()));
+                let mut next_placeholder = 0usize;
+                let where_clause = selection.as_ref().map(|expr| {
+                    let mut expr = expr.clone();
+                    Self::number_placeholders(&mut expr, &mut next_placeholder);
+                    Box::new(expr)
+                });
 
                 Ok(Statement::Delete {
                     table,
                     where_clause,
+                    returning: Self::convert_returning(returning)?,
                 })
             }
-            SqlStatement::Query(query) => {
-                if let ast::SetExpr::Select(select) = &*query.body {
-                    let table =
-                        if let Some(ast::TableWithJoins { relation, .. }) = select.from.first() {
-                            if let ast::TableFactor::Table { name, .. } = relation {
-                                name.to_string()
-                            } else {
-                                return Err(anyhow!("Unsupported table reference"));
-                            }
-                        } else {
-                            return Err(anyhow!("No table specified"));
-                        };
-
-                    let projection = select
-                        .projection
-                        .iter()
-                        .map(|proj| match proj {
-                            ast::SelectItem::Wildcard(_) => Ok(SelectItem::Wildcard),
-                            ast::SelectItem::UnnamedExpr(expr) => match expr {
-                                Expr::Identifier(ident) => Ok(SelectItem::Column {
-                                    name: ident.value.clone(),
-                                    alias: None,
-                                }),
-                                _ => Err(anyhow!("Unsupported select expression: {}", expr)),
-                            },
-                            ast::SelectItem::ExprWithAlias { expr, alias } => match expr {
-                                Expr::Identifier(ident) => Ok(SelectItem::Column {
-                                    name: ident.value.clone(),
-                                    alias: Some(alias.value.clone()),
-                                }),
-                                _ => Err(anyhow!("Unsupported select expression: {}", expr)),
-                            },
-                            _ => Err(anyhow!("Unsupported select item")),
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-
-                    let where_clause = select.selection.as_ref().map(|expr| Box::new(expr.clone()));
-
-                    let order_by = if !query.order_by.is_empty() {
-                        Some(
-                            query
-                                .order_by
-                                .iter()
-                                .map(|order| {
-                                    let column = match &order.expr {
-                                        Expr::Identifier(ident) => ident.value.clone(),
-                                        _ => {
-                                            return Err(anyhow!(
-                                                "Unsupported ORDER BY expression: {}",
-                                                order.expr
-                                            ))
-                                        }
-                                    };
-                                    let ascending = order.asc.unwrap_or(true);
-                                    Ok(crate::sql::types::OrderByClause { column, ascending })
-                                })
-                                .collect::<Result<Vec<_>>>()?,
-                        )
+            SqlStatement::Query(query) => Self::convert_query(query),
+            SqlStatement::AlterTable {
+                name, operations, ..
+            } => {
+                let table_name = name.to_string();
+                let ops = operations
+                    .iter()
+                    .map(Self::convert_alter_operation)
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Statement::AlterTable {
+                    name: table_name,
+                    operations: ops,
+                })
+            }
+            _ => Err(anyhow!("Unsupported statement type")),
+        }
+    }
+
+    /// Converts a `SELECT` query body into a `Statement::Select`. Shared by a bare
+    /// `SELECT ...` statement and the `query` half of `CREATE TABLE ... AS SELECT`.
+    fn convert_query(query: &ast::Query) -> Result<Statement> {
+        if let ast::SetExpr::Select(select) = &*query.body {
+            let (table, joins) = if let Some(table_with_joins) = select.from.first() {
+                let table =
+                    if let ast::TableFactor::Table { name, .. } = &table_with_joins.relation {
+                        name.to_string()
                     } else {
-                        None
+                        return Err(anyhow!("Unsupported table reference"));
                     };
 
-                    let limit = query.limit.as_ref().and_then(|limit_expr| {
-                        if let ast::Expr::Value(ast::Value::Number(n, _)) = limit_expr {
-                            n.parse().ok()
-                        } else {
-                            None
-                        }
-                    });
+                let joins = table_with_joins
+                    .joins
+                    .iter()
+                    .map(|join| Self::convert_join(&table, join))
+                    .collect::<Result<Vec<_>>>()?;
 
-                    Ok(Statement::Select {
-                        table,
-                        projection,
-                        where_clause,
-                        order_by,
-                        limit,
-                    })
+                (table, joins)
+            } else {
+                return Err(anyhow!("No table specified"));
+            };
+
+            let projection = select
+                .projection
+                .iter()
+                .map(|proj| match proj {
+                    ast::SelectItem::Wildcard(_) => Ok(SelectItem::Wildcard),
+                    ast::SelectItem::UnnamedExpr(expr) => Self::convert_select_item(expr, None),
+                    ast::SelectItem::ExprWithAlias { expr, alias } => {
+                        Self::convert_select_item(expr, Some(alias.value.clone()))
+                    }
+                    _ => Err(anyhow!("Unsupported select item")),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            // One counter numbers bare `?` tokens across both clauses, so a
+            // placeholder used only in HAVING (e.g. `HAVING COUNT(*) > ?`)
+            // doesn't collide with one already numbered in WHERE.
+            let mut next_placeholder = 0usize;
+            let where_clause = select.selection.as_ref().map(|expr| {
+                let mut expr = expr.clone();
+                Self::number_placeholders(&mut expr, &mut next_placeholder);
+                Box::new(expr)
+            });
+
+            let group_by = select
+                .group_by
+                .iter()
+                .map(|expr| match expr {
+                    Expr::Identifier(ident) => Ok(ident.value.clone()),
+                    Expr::CompoundIdentifier(idents) => Ok(Self::join_dotted(idents)),
+                    other => Err(anyhow!("Unsupported GROUP BY expression: {}", other)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let having = select.having.as_ref().map(|expr| {
+                let mut expr = expr.clone();
+                Self::number_placeholders(&mut expr, &mut next_placeholder);
+                Box::new(expr)
+            });
+
+            let order_by = if !query.order_by.is_empty() {
+                Some(
+                    query
+                        .order_by
+                        .iter()
+                        .map(|order| {
+                            let column = match &order.expr {
+                                Expr::Identifier(ident) => ident.value.clone(),
+                                _ => {
+                                    return Err(anyhow!(
+                                        "Unsupported ORDER BY expression: {}",
+                                        order.expr
+                                    ))
+                                }
+                            };
+                            let ascending = order.asc.unwrap_or(true);
+                            Ok(crate::sql::types::OrderByClause { column, ascending })
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                )
+            } else {
+                None
+            };
+
+            let limit = query.limit.as_ref().and_then(|limit_expr| {
+                if let ast::Expr::Value(ast::Value::Number(n, _)) = limit_expr {
+                    n.parse().ok()
                 } else {
-                    Err(anyhow!("Unsupported query type"))
+                    None
                 }
+            });
+
+            Ok(Statement::Select {
+                table,
+                joins,
+                projection,
+                where_clause,
+                group_by,
+                having,
+                order_by,
+                limit,
+            })
+        } else {
+            Err(anyhow!("Unsupported query type"))
+        }
+    }
+
+    /// Converts one `ALTER TABLE` clause. Operations this repo doesn't model yet
+    /// (adding/dropping constraints, changing a column's type, ...) are rejected
+    /// rather than silently ignored.
+    fn convert_alter_operation(op: &ast::AlterTableOperation) -> Result<AlterTableOperation> {
+        match op {
+            ast::AlterTableOperation::AddColumn { column_def, .. } => Ok(AlterTableOperation::AddColumn {
+                column: Self::convert_column(column_def)?,
+            }),
+            ast::AlterTableOperation::DropColumn { column_name, .. } => {
+                Ok(AlterTableOperation::DropColumn {
+                    name: column_name.to_string(),
+                })
             }
-            _ => Err(anyhow!("Unsupported statement type")),
+            ast::AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => Ok(AlterTableOperation::RenameColumn {
+                old_name: old_column_name.to_string(),
+                new_name: new_column_name.to_string(),
+            }),
+            ast::AlterTableOperation::RenameTable { table_name } => {
+                Ok(AlterTableOperation::RenameTable {
+                    new_name: table_name.to_string(),
+                })
+            }
+            other => Err(anyhow!("Unsupported ALTER TABLE operation: {:?}", other)),
         }
     }
 
@@ -243,9 +396,64 @@ impl Parser {
             }));
         }
 
+        if tokens.len() >= 5
+            && tokens[0].eq_ignore_ascii_case("create")
+            && tokens[1].eq_ignore_ascii_case("index")
+        {
+            return Ok(Some(Self::parse_create_index(&tokens)?));
+        }
+
+        if tokens[0].eq_ignore_ascii_case("begin")
+            && (tokens.len() == 1
+                || (tokens.len() == 2 && tokens[1].eq_ignore_ascii_case("transaction")))
+        {
+            return Ok(Some(Statement::Begin));
+        }
+
+        if tokens.len() == 1 && tokens[0].eq_ignore_ascii_case("commit") {
+            return Ok(Some(Statement::Commit));
+        }
+
+        if tokens.len() == 1 && tokens[0].eq_ignore_ascii_case("rollback") {
+            return Ok(Some(Statement::Rollback));
+        }
+
         Ok(None)
     }
 
+    /// Hand-parses `CREATE INDEX name ON table(column)` rather than routing it
+    /// through `sqlparser`, same as the other management statements above: the
+    /// syntax is a fixed shape, so a token scan is simpler than reconciling
+    /// `sqlparser`'s own `CREATE INDEX` AST with this crate's `Statement`.
+    fn parse_create_index(tokens: &[&str]) -> Result<Statement> {
+        let on_pos = tokens
+            .iter()
+            .position(|t| t.eq_ignore_ascii_case("on"))
+            .ok_or_else(|| anyhow!("Expected CREATE INDEX name ON table(column)"))?;
+        // `on_pos` must leave room for a name token between `INDEX` (tokens[1])
+        // and `ON` itself, otherwise `ON` is sitting where the name should be
+        // (e.g. `CREATE INDEX ON users(age)`, which must be rejected rather
+        // than silently naming the index "ON").
+        if on_pos < 3 || on_pos + 1 >= tokens.len() {
+            return Err(anyhow!("Expected CREATE INDEX name ON table(column)"));
+        }
+
+        let name = Self::clean_identifier(tokens[2]);
+        let target: String = tokens[on_pos + 1..].concat();
+        let open = target
+            .find('(')
+            .ok_or_else(|| anyhow!("Expected CREATE INDEX name ON table(column)"))?;
+        let close = target
+            .rfind(')')
+            .ok_or_else(|| anyhow!("Expected CREATE INDEX name ON table(column)"))?;
+
+        Ok(Statement::CreateIndex {
+            name,
+            table: Self::clean_identifier(&target[..open]),
+            column: Self::clean_identifier(&target[open + 1..close]),
+        })
+    }
+
     fn clean_identifier(raw: &str) -> String {
         let trimmed = raw.trim();
         if trimmed.len() >= 2 {
@@ -261,10 +469,240 @@ impl Parser {
         trimmed.to_string()
     }
 
+    /// Converts one projection expression (already split from its optional alias)
+    /// into a `SelectItem`: bare/compound identifiers become `Column`, a
+    /// recognized aggregate call becomes `Aggregate`, anything else is a computed
+    /// `Expr`.
+    fn convert_select_item(expr: &Expr, alias: Option<String>) -> Result<SelectItem> {
+        match expr {
+            Expr::Identifier(ident) => Ok(SelectItem::Column {
+                name: ident.value.clone(),
+                alias,
+            }),
+            Expr::CompoundIdentifier(idents) => Ok(SelectItem::Column {
+                name: Self::join_dotted(idents),
+                alias,
+            }),
+            Expr::Function(function) => match Self::convert_aggregate(function)? {
+                Some((func, column)) => Ok(SelectItem::Aggregate {
+                    func,
+                    column,
+                    alias,
+                }),
+                None => Ok(SelectItem::Expr {
+                    expr: Box::new(expr.clone()),
+                    alias,
+                }),
+            },
+            other => Ok(SelectItem::Expr {
+                expr: Box::new(other.clone()),
+                alias,
+            }),
+        }
+    }
+
+    /// Converts an `INSERT`/`DELETE`'s optional `RETURNING` clause into a plain
+    /// list of column names. Only bare/compound identifiers are supported (no
+    /// computed expressions or aliases), matching the simple `RETURNING col, ...`
+    /// shape the executor projects rows against.
+    fn convert_returning(returning: &Option<Vec<ast::SelectItem>>) -> Result<Option<Vec<String>>> {
+        let Some(items) = returning else {
+            return Ok(None);
+        };
+
+        items
+            .iter()
+            .map(|item| match item {
+                ast::SelectItem::UnnamedExpr(Expr::Identifier(ident)) => Ok(ident.value.clone()),
+                ast::SelectItem::UnnamedExpr(Expr::CompoundIdentifier(idents)) => {
+                    Ok(Self::join_dotted(idents))
+                }
+                other => Err(anyhow!("Unsupported RETURNING item: {:?}", other)),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+
+    /// Recognizes `COUNT`/`SUM`/`AVG`/`MIN`/`MAX` calls, returning `None` for any
+    /// other function name so the caller falls back to treating it as a scalar
+    /// expression (evaluated by `ExpressionEvaluator::evaluate_function`).
+    pub(crate) fn convert_aggregate(
+        function: &ast::Function,
+    ) -> Result<Option<(AggregateFunc, Option<String>)>> {
+        let name = function.name.to_string().to_uppercase();
+        let func = match name.as_str() {
+            "COUNT" => AggregateFunc::Count,
+            "SUM" => AggregateFunc::Sum,
+            "AVG" => AggregateFunc::Avg,
+            "MIN" => AggregateFunc::Min,
+            "MAX" => AggregateFunc::Max,
+            _ => return Ok(None),
+        };
+
+        let column = match function.args.as_slice() {
+            [ast::FunctionArg::Unnamed(ast::FunctionArgExpr::Wildcard)] => {
+                if func != AggregateFunc::Count {
+                    return Err(anyhow!("{} does not accept * as an argument", name));
+                }
+                None
+            }
+            [ast::FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(Expr::Identifier(ident)))] => {
+                Some(ident.value.clone())
+            }
+            [ast::FunctionArg::Unnamed(ast::FunctionArgExpr::Expr(Expr::CompoundIdentifier(
+                idents,
+            )))] => Some(Self::join_dotted(idents)),
+            _ => return Err(anyhow!("{} takes exactly one column or * argument", name)),
+        };
+
+        Ok(Some((func, column)))
+    }
+
+    /// Renders a compound identifier (`table.col`, or a JSON-navigation path) back
+    /// to its dotted source text, for storing as a `SelectItem::Column` name.
+    fn join_dotted(idents: &[Ident]) -> String {
+        idents
+            .iter()
+            .map(|i| i.value.clone())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Converts one `ast::Join` into our `JoinClause`, resolving `USING (cols)`
+    /// into an equivalent `base_table.col = joined_table.col` equality chain so
+    /// downstream code only ever has to deal with an `ON` predicate.
+    fn convert_join(base_table: &str, join: &ast::Join) -> Result<JoinClause> {
+        let relation = match &join.relation {
+            ast::TableFactor::Table { name, .. } => name.to_string(),
+            other => return Err(anyhow!("Unsupported join relation: {:?}", other)),
+        };
+
+        let (kind, constraint) = match &join.join_operator {
+            ast::JoinOperator::Inner(c) => (JoinKind::Inner, Some(c)),
+            ast::JoinOperator::LeftOuter(c) => (JoinKind::Left, Some(c)),
+            ast::JoinOperator::RightOuter(c) => (JoinKind::Right, Some(c)),
+            ast::JoinOperator::FullOuter(c) => (JoinKind::Full, Some(c)),
+            ast::JoinOperator::CrossJoin => (JoinKind::Cross, None),
+            other => return Err(anyhow!("Unsupported join type: {:?}", other)),
+        };
+
+        let on = match constraint {
+            Some(ast::JoinConstraint::On(expr)) => Some(Box::new(expr.clone())),
+            Some(ast::JoinConstraint::Using(columns)) => {
+                let mut combined: Option<Expr> = None;
+                for column in columns {
+                    let eq = Expr::BinaryOp {
+                        left: Box::new(Expr::CompoundIdentifier(vec![
+                            Ident::new(base_table),
+                            column.clone(),
+                        ])),
+                        op: BinaryOperator::Eq,
+                        right: Box::new(Expr::CompoundIdentifier(vec![
+                            Ident::new(&relation),
+                            column.clone(),
+                        ])),
+                    };
+                    combined = Some(match combined {
+                        Some(acc) => Expr::BinaryOp {
+                            left: Box::new(acc),
+                            op: BinaryOperator::And,
+                            right: Box::new(eq),
+                        },
+                        None => eq,
+                    });
+                }
+                combined.map(Box::new)
+            }
+            Some(ast::JoinConstraint::Natural) | Some(ast::JoinConstraint::None) | None => None,
+        };
+
+        Ok(JoinClause { relation, kind, on })
+    }
+
     fn convert_column(col: &ColumnDef) -> Result<Column> {
         let name = col.name.to_string();
         let data_type = Self::convert_data_type(&col.data_type)?;
-        Ok(Column { name, data_type })
+
+        let mut column = Column::new(name, data_type);
+
+        for option in &col.options {
+            match &option.option {
+                ColumnOption::NotNull => column.not_null = true,
+                ColumnOption::Unique { is_primary, .. } => {
+                    column.unique = true;
+                    column.primary_key = column.primary_key || *is_primary;
+                }
+                ColumnOption::Default(expr) => {
+                    column.default = Some(Self::convert_expr(expr, &mut 0)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(column)
+    }
+
+    /// Folds one table-level constraint into `columns`' flags (single-column
+    /// `UNIQUE`/`PRIMARY KEY`) or into `unique_groups`/`foreign_keys` (everything
+    /// that spans more than one column, or a `FOREIGN KEY`).
+    fn apply_table_constraint(
+        constraint: &TableConstraint,
+        columns: &mut [Column],
+        unique_groups: &mut Vec<Vec<String>>,
+        foreign_keys: &mut Vec<ForeignKey>,
+    ) -> Result<()> {
+        match constraint {
+            TableConstraint::Unique { columns: cols, .. } => {
+                Self::record_unique_group(cols, false, columns, unique_groups);
+            }
+            TableConstraint::PrimaryKey { columns: cols, .. } => {
+                Self::record_unique_group(cols, true, columns, unique_groups);
+            }
+            TableConstraint::ForeignKey {
+                columns: cols,
+                foreign_table,
+                referred_columns,
+                on_delete,
+                ..
+            } => {
+                foreign_keys.push(ForeignKey {
+                    columns: cols.iter().map(|i| i.value.clone()).collect(),
+                    ref_table: foreign_table.to_string(),
+                    ref_columns: referred_columns.iter().map(|i| i.value.clone()).collect(),
+                    on_delete: Self::convert_referential_action(*on_delete),
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn record_unique_group(
+        cols: &[Ident],
+        is_primary: bool,
+        columns: &mut [Column],
+        unique_groups: &mut Vec<Vec<String>>,
+    ) {
+        if let [single] = cols {
+            if let Some(column) = columns.iter_mut().find(|c| c.name == single.value) {
+                column.unique = true;
+                column.primary_key = column.primary_key || is_primary;
+                return;
+            }
+        }
+
+        unique_groups.push(cols.iter().map(|i| i.value.clone()).collect());
+    }
+
+    fn convert_referential_action(action: Option<SqlReferentialAction>) -> ReferentialAction {
+        match action {
+            None | Some(SqlReferentialAction::NoAction) => ReferentialAction::NoAction,
+            Some(SqlReferentialAction::Restrict) => ReferentialAction::Restrict,
+            Some(SqlReferentialAction::Cascade) => ReferentialAction::Cascade,
+            Some(SqlReferentialAction::SetNull) => ReferentialAction::SetNull,
+            Some(SqlReferentialAction::SetDefault) => ReferentialAction::SetDefault,
+        }
     }
 
     fn convert_data_type(data_type: &SqlDataType) -> Result<DataType> {
@@ -278,11 +716,16 @@ impl Parser {
             | SqlDataType::Char(_)
             | SqlDataType::String(_) => Ok(DataType::Text),
             SqlDataType::Boolean => Ok(DataType::Boolean),
+            SqlDataType::JSON => Ok(DataType::Json),
             _ => Err(anyhow!("Unsupported data type: {:?}", data_type)),
         }
     }
 
-    fn convert_expr(expr: &Expr) -> Result<Value> {
+    /// Converts a literal expression into a `Value`, or a `Value::Placeholder`
+    /// for a `$1`/`?` parameter token. `next_placeholder` numbers bare `?`
+    /// tokens in first-appearance order (`$N` tokens are numbered by `N`
+    /// directly); pass a fresh counter per statement.
+    fn convert_expr(expr: &Expr, next_placeholder: &mut usize) -> Result<Value> {
         match expr {
             Expr::Value(ast::Value::Number(n, _)) => {
                 if n.contains('.') {
@@ -292,12 +735,92 @@ impl Parser {
                 }
             }
             Expr::Value(ast::Value::SingleQuotedString(s))
-            | Expr::Value(ast::Value::DoubleQuotedString(s)) => Ok(Value::Text(s.clone())),
+            | Expr::Value(ast::Value::DoubleQuotedString(s)) => {
+                // A string literal that parses as a JSON object or array is stored as
+                // a navigable JSON document rather than opaque text.
+                match serde_json::from_str::<serde_json::Value>(s) {
+                    Ok(json) if json.is_object() || json.is_array() => Ok(Value::Json(json)),
+                    _ => Ok(Value::Text(s.clone())),
+                }
+            }
             Expr::Value(ast::Value::Boolean(b)) => Ok(Value::Boolean(*b)),
             Expr::Value(ast::Value::Null) => Ok(Value::Null),
+            Expr::Value(ast::Value::Placeholder(token)) => {
+                Ok(Value::Placeholder(Self::placeholder_index(token, next_placeholder)?))
+            }
             _ => Err(anyhow!("Unsupported expression: {:?}", expr)),
         }
     }
+
+    /// Rewrites every bare `?` placeholder inside `expr` into an explicit `$N`
+    /// token numbered by `next_placeholder` (continuing whatever count the
+    /// caller has already reached elsewhere in the same statement), so a
+    /// later clause's placeholders never restart their own numbering from
+    /// scratch. An already-explicit `$N` token is left untouched.
+    fn number_placeholders(expr: &mut Expr, next_placeholder: &mut usize) {
+        if let Expr::Value(ast::Value::Placeholder(token)) = expr {
+            if !token.starts_with('$') {
+                let idx = *next_placeholder;
+                *next_placeholder += 1;
+                *token = format!("${}", idx + 1);
+            }
+            return;
+        }
+
+        match expr {
+            Expr::BinaryOp { left, right, .. } => {
+                Self::number_placeholders(left, next_placeholder);
+                Self::number_placeholders(right, next_placeholder);
+            }
+            Expr::UnaryOp { expr, .. } => Self::number_placeholders(expr, next_placeholder),
+            Expr::Nested(inner) | Expr::IsNull(inner) | Expr::IsNotNull(inner) => {
+                Self::number_placeholders(inner, next_placeholder)
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                Self::number_placeholders(expr, next_placeholder);
+                Self::number_placeholders(pattern, next_placeholder);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                Self::number_placeholders(expr, next_placeholder);
+                Self::number_placeholders(low, next_placeholder);
+                Self::number_placeholders(high, next_placeholder);
+            }
+            Expr::InList { expr, list, .. } => {
+                Self::number_placeholders(expr, next_placeholder);
+                for item in list {
+                    Self::number_placeholders(item, next_placeholder);
+                }
+            }
+            Expr::AnyOp { left, right, .. } | Expr::AllOp { left, right, .. } => {
+                Self::number_placeholders(left, next_placeholder);
+                Self::number_placeholders(right, next_placeholder);
+            }
+            _ => {}
+        }
+    }
+
+    /// Parses a placeholder token into a 0-based index: `"$1"` -> `0`, `"$2"` ->
+    /// `1`, and a bare `"?"` -> whatever `next_placeholder` currently holds
+    /// (then advances it), since the token itself carries no position. Shared
+    /// with `PreparedStatement`, which walks the same `$N`/`?` tokens back out
+    /// of a WHERE/HAVING expression tree once this statement has been bound.
+    pub(crate) fn placeholder_index(token: &str, next_placeholder: &mut usize) -> Result<usize> {
+        if let Some(digits) = token.strip_prefix('$') {
+            let n: usize = digits
+                .parse()
+                .map_err(|_| anyhow!("Invalid placeholder token: {}", token))?;
+            if n == 0 {
+                return Err(anyhow!("Placeholder index must start at 1, found $0"));
+            }
+            Ok(n - 1)
+        } else {
+            let idx = *next_placeholder;
+            *next_placeholder += 1;
+            Ok(idx)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -310,7 +833,7 @@ mod tests {
         let stmt = Parser::parse(sql).unwrap();
 
         match stmt {
-            Statement::CreateTable { name, columns } => {
+            Statement::CreateTable { name, columns, .. } => {
                 assert_eq!(name, "users");
                 assert_eq!(columns.len(), 3);
                 assert_eq!(columns[0].name, "id");
@@ -320,6 +843,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_create_table_with_constraints() {
+        let sql = "CREATE TABLE orders (\
+            id INTEGER PRIMARY KEY, \
+            customer_id INTEGER NOT NULL, \
+            status TEXT DEFAULT 'pending', \
+            sku TEXT UNIQUE, \
+            FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE CASCADE\
+        )";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::CreateTable {
+                columns,
+                foreign_keys,
+                unique_groups,
+                ..
+            } => {
+                assert!(columns[0].primary_key);
+                assert!(columns[0].unique);
+                assert!(columns[1].not_null);
+                assert_eq!(columns[2].default, Some(Value::Text("pending".to_string())));
+                assert!(columns[3].unique);
+                assert!(unique_groups.is_empty());
+
+                assert_eq!(foreign_keys.len(), 1);
+                assert_eq!(foreign_keys[0].columns, vec!["customer_id".to_string()]);
+                assert_eq!(foreign_keys[0].ref_table, "customers");
+                assert_eq!(foreign_keys[0].ref_columns, vec!["id".to_string()]);
+                assert_eq!(foreign_keys[0].on_delete, ReferentialAction::Cascade);
+            }
+            _ => panic!("Expected CreateTable statement"),
+        }
+    }
+
     #[test]
     fn test_parse_insert() {
         let sql = "INSERT INTO users (id, name) VALUES (1, 'Alice'), (2, 'Bob')";
@@ -330,15 +888,56 @@ mod tests {
                 table,
                 columns,
                 values,
+                returning,
             } => {
                 assert_eq!(table, "users");
                 assert_eq!(columns.len(), 2);
                 assert_eq!(values.len(), 2);
+                assert_eq!(returning, None);
             }
             _ => panic!("Expected Insert statement"),
         }
     }
 
+    #[test]
+    fn test_parse_insert_with_placeholders() {
+        let sql = "INSERT INTO users (id, name) VALUES ($1, $2)";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Insert { values, .. } => {
+                assert_eq!(values[0], vec![Value::Placeholder(0), Value::Placeholder(1)]);
+            }
+            _ => panic!("Expected Insert statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_insert_with_returning() {
+        let sql = "INSERT INTO users (id, name) VALUES (1, 'Alice') RETURNING id, name";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Insert { returning, .. } => {
+                assert_eq!(returning, Some(vec!["id".to_string(), "name".to_string()]));
+            }
+            _ => panic!("Expected Insert statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_delete_with_returning() {
+        let sql = "DELETE FROM users WHERE id = 1 RETURNING id";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Delete { returning, .. } => {
+                assert_eq!(returning, Some(vec!["id".to_string()]));
+            }
+            _ => panic!("Expected Delete statement"),
+        }
+    }
+
     #[test]
     fn test_parse_select() {
         let sql = "SELECT id, name FROM users";
@@ -355,6 +954,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_select_with_inner_join() {
+        let sql = "SELECT u.name, o.total FROM users u INNER JOIN orders o ON u.id = o.user_id";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Select { table, joins, .. } => {
+                assert_eq!(table, "users");
+                assert_eq!(joins.len(), 1);
+                assert_eq!(joins[0].relation, "o");
+                assert_eq!(joins[0].kind, JoinKind::Inner);
+                assert!(joins[0].on.is_some());
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_select_with_left_join_using() {
+        let sql = "SELECT * FROM users LEFT JOIN orders USING (id)";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Select { joins, .. } => {
+                assert_eq!(joins.len(), 1);
+                assert_eq!(joins[0].kind, JoinKind::Left);
+                assert!(joins[0].on.is_some());
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_select_with_count_star_and_group_by() {
+        let sql = "SELECT department, COUNT(*) FROM employees GROUP BY department HAVING COUNT(*) > 1";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Select {
+                projection,
+                group_by,
+                having,
+                ..
+            } => {
+                assert_eq!(group_by, vec!["department".to_string()]);
+                assert!(having.is_some());
+                assert_eq!(projection.len(), 2);
+                match &projection[1] {
+                    SelectItem::Aggregate { func, column, .. } => {
+                        assert_eq!(*func, AggregateFunc::Count);
+                        assert!(column.is_none());
+                    }
+                    _ => panic!("Expected aggregate projection"),
+                }
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_select_aggregate_over_column_with_alias() {
+        let sql = "SELECT AVG(salary) AS avg_salary FROM employees";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Select { projection, .. } => {
+                assert_eq!(projection.len(), 1);
+                match &projection[0] {
+                    SelectItem::Aggregate {
+                        func,
+                        column,
+                        alias,
+                    } => {
+                        assert_eq!(*func, AggregateFunc::Avg);
+                        assert_eq!(column.as_deref(), Some("salary"));
+                        assert_eq!(alias.as_deref(), Some("avg_salary"));
+                    }
+                    _ => panic!("Expected aggregate projection"),
+                }
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
     #[test]
     fn test_parse_select_wildcard() {
         let sql = "SELECT * FROM users";
@@ -406,7 +1089,7 @@ mod tests {
                 assert_eq!(table, "users");
                 assert_eq!(assignments.len(), 1);
                 assert_eq!(assignments[0].0, "name");
-                assert_eq!(assignments[0].1, Value::Text("Bob".to_string()));
+                assert_eq!(assignments[0].1, Expr::Value(ast::Value::SingleQuotedString("Bob".to_string())));
                 assert!(where_clause.is_some());
             }
             _ => panic!("Expected Update statement"),
@@ -422,9 +1105,11 @@ mod tests {
             Statement::Delete {
                 table,
                 where_clause,
+                returning,
             } => {
                 assert_eq!(table, "users");
                 assert!(where_clause.is_some());
+                assert_eq!(returning, None);
             }
             _ => panic!("Expected Delete statement"),
         }
@@ -444,9 +1129,9 @@ mod tests {
                 assert_eq!(table, "users");
                 assert_eq!(assignments.len(), 2);
                 assert_eq!(assignments[0].0, "name");
-                assert_eq!(assignments[0].1, Value::Text("Bob".to_string()));
+                assert_eq!(assignments[0].1, Expr::Value(ast::Value::SingleQuotedString("Bob".to_string())));
                 assert_eq!(assignments[1].0, "age");
-                assert_eq!(assignments[1].1, Value::Integer(30));
+                assert_eq!(assignments[1].1, Expr::Value(ast::Value::Number("30".to_string(), false)));
                 assert!(where_clause.is_some());
             }
             _ => panic!("Expected Update statement"),
@@ -467,13 +1152,42 @@ mod tests {
                 assert_eq!(table, "users");
                 assert_eq!(assignments.len(), 1);
                 assert_eq!(assignments[0].0, "active");
-                assert_eq!(assignments[0].1, Value::Boolean(true));
+                assert_eq!(assignments[0].1, Expr::Value(ast::Value::Boolean(true)));
                 assert!(where_clause.is_none());
             }
             _ => panic!("Expected Update statement"),
         }
     }
 
+    #[test]
+    fn test_parse_update_numbers_where_placeholder_after_set_placeholders() {
+        let sql = "UPDATE users SET name = ? WHERE id = ?";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::Update {
+                assignments,
+                where_clause,
+                ..
+            } => {
+                match &assignments[0].1 {
+                    Expr::Value(ast::Value::Placeholder(token)) => assert_eq!(token, "$1"),
+                    other => panic!("Expected a placeholder, got {:?}", other),
+                }
+                match where_clause.unwrap().as_ref() {
+                    Expr::BinaryOp { right, .. } => match right.as_ref() {
+                        Expr::Value(ast::Value::Placeholder(token)) => {
+                            assert_eq!(token, "$2");
+                        }
+                        other => panic!("Expected a placeholder, got {:?}", other),
+                    },
+                    other => panic!("Expected a binary comparison, got {:?}", other),
+                }
+            }
+            _ => panic!("Expected Update statement"),
+        }
+    }
+
     #[test]
     fn test_parse_delete_without_where() {
         let sql = "DELETE FROM users";
@@ -483,9 +1197,11 @@ mod tests {
             Statement::Delete {
                 table,
                 where_clause,
+                returning,
             } => {
                 assert_eq!(table, "users");
                 assert!(where_clause.is_none());
+                assert_eq!(returning, None);
             }
             _ => panic!("Expected Delete statement"),
         }
@@ -515,6 +1231,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_alter_table_add_column() {
+        let sql = "ALTER TABLE users ADD COLUMN age INTEGER";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::AlterTable { name, operations } => {
+                assert_eq!(name, "users");
+                assert_eq!(operations.len(), 1);
+                match &operations[0] {
+                    AlterTableOperation::AddColumn { column } => {
+                        assert_eq!(column.name, "age");
+                        assert_eq!(column.data_type, DataType::Integer);
+                    }
+                    _ => panic!("Expected AddColumn operation"),
+                }
+            }
+            _ => panic!("Expected AlterTable statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_alter_table_rename() {
+        let sql = "ALTER TABLE users RENAME TO people";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::AlterTable { name, operations } => {
+                assert_eq!(name, "users");
+                match &operations[0] {
+                    AlterTableOperation::RenameTable { new_name } => {
+                        assert_eq!(new_name, "people");
+                    }
+                    _ => panic!("Expected RenameTable operation"),
+                }
+            }
+            _ => panic!("Expected AlterTable statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_create_table_as_select() {
+        let sql = "CREATE TABLE active_users AS SELECT id, name FROM users WHERE active = true";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::CreateTableAs {
+                name,
+                query,
+                temporary,
+            } => {
+                assert_eq!(name, "active_users");
+                assert!(!temporary);
+                match *query {
+                    Statement::Select { table, projection, .. } => {
+                        assert_eq!(table, "users");
+                        assert_eq!(projection.len(), 2);
+                    }
+                    _ => panic!("Expected Select query"),
+                }
+            }
+            _ => panic!("Expected CreateTableAs statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_create_temporary_table_as_select() {
+        let sql = "CREATE TEMPORARY TABLE recent AS SELECT * FROM users";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::CreateTableAs { temporary, .. } => assert!(temporary),
+            _ => panic!("Expected CreateTableAs statement"),
+        }
+    }
+
     #[test]
     fn test_parse_drop_table_if_exists() {
         let sql = "DROP TABLE IF EXISTS users";
@@ -528,4 +1320,71 @@ mod tests {
             _ => panic!("Expected DropTable statement"),
         }
     }
+
+    #[test]
+    fn test_parse_create_index() {
+        let sql = "CREATE INDEX idx_users_age ON users(age)";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::CreateIndex { name, table, column } => {
+                assert_eq!(name, "idx_users_age");
+                assert_eq!(table, "users");
+                assert_eq!(column, "age");
+            }
+            _ => panic!("Expected CreateIndex statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_create_index_with_space_before_paren() {
+        let sql = "CREATE INDEX idx ON users (age)";
+        let stmt = Parser::parse(sql).unwrap();
+
+        match stmt {
+            Statement::CreateIndex { table, column, .. } => {
+                assert_eq!(table, "users");
+                assert_eq!(column, "age");
+            }
+            _ => panic!("Expected CreateIndex statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_create_index_without_name_is_rejected() {
+        let sql = "CREATE INDEX ON users(age)";
+        let err = Parser::parse(sql).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected CREATE INDEX name ON table(column)"));
+    }
+
+    #[test]
+    fn test_parse_begin_commit_rollback() {
+        assert!(matches!(Parser::parse("BEGIN").unwrap(), Statement::Begin));
+        assert!(matches!(
+            Parser::parse("BEGIN TRANSACTION").unwrap(),
+            Statement::Begin
+        ));
+        assert!(matches!(Parser::parse("COMMIT").unwrap(), Statement::Commit));
+        assert!(matches!(
+            Parser::parse("ROLLBACK").unwrap(),
+            Statement::Rollback
+        ));
+    }
+
+    #[test]
+    fn test_parse_explain_wraps_inner_statement() {
+        match Parser::parse("EXPLAIN SELECT * FROM users WHERE id = 1").unwrap() {
+            Statement::Explain { query } => {
+                assert!(matches!(*query, Statement::Select { .. }));
+            }
+            _ => panic!("Expected Explain statement"),
+        }
+    }
+
+    #[test]
+    fn test_parse_explain_requires_a_statement() {
+        assert!(Parser::parse("EXPLAIN").is_err());
+    }
 }
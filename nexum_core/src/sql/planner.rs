@@ -1,4 +1,21 @@
-use super::types::Statement;
+use super::binder::{BoundColumn, Binder};
+use super::types::{
+    CompareOp, JoinClause, JoinKind, OrderByClause, Predicate, ReferentialAction, SelectItem,
+    Statement, Value,
+};
+use crate::catalog::Catalog;
+use crate::storage::{Result, StorageError};
+use sqlparser::ast::{BinaryOperator, Expr};
+
+/// A referential check `Plan::Delete` must run before (`Restrict`) or along
+/// with (`Cascade`) removing the parent row: `child_table.child_column`
+/// references the table being deleted from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FkCheck {
+    pub child_table: String,
+    pub child_column: String,
+    pub action: ReferentialAction,
+}
 
 #[derive(Debug, Clone)]
 pub enum Plan {
@@ -10,40 +27,1258 @@ pub enum Plan {
         table: String,
         rows: usize,
     },
-    Select {
+    /// A `SELECT`'s operator tree: a `PlanNode::Scan` at the leaf, wrapped by
+    /// whatever `JOIN`/`GROUP BY`/`ORDER BY`/`LIMIT`/projection clauses the
+    /// query used. See `PlanNode` for why this is a tree rather than a flat
+    /// summary.
+    Select(PlanNode),
+    Update {
         table: String,
-        columns: Vec<String>,
+        /// Column/expression pairs validated against the catalog: every
+        /// column is confirmed to exist on `table` before execution is
+        /// attempted. The expression is evaluated per row by the executor, so
+        /// it may reference other columns (`price = price * 2`) rather than
+        /// always being a literal.
+        assignments: Vec<(String, Expr)>,
+        /// Whether any assignment targets a primary-key column, so the
+        /// executor knows this update may have to move the row's index and
+        /// FK-reference entries rather than just overwrite its values.
+        assigns_primary_key: bool,
+        /// The WHERE clause lowered into the typed `Predicate` IR; `None`
+        /// means every row in `table` is updated.
+        filter: Option<Predicate>,
     },
     Delete {
         table: String,
-        has_where: bool,
+        /// Every other table with a foreign key into `table`, and the action
+        /// the executor must take for each deleted row.
+        fk_checks: Vec<FkCheck>,
+    },
+    ShowTables,
+    DescribeTable {
+        name: String,
+    },
+    DropTable {
+        name: String,
+    },
+    AlterTable {
+        name: String,
+        operation_count: usize,
+    },
+    CreateTableAs {
+        name: String,
+        temporary: bool,
+    },
+    CreateIndex {
+        name: String,
+        table: String,
+        column: String,
+    },
+    /// The plan `EXPLAIN` reports for its inner statement, without running it.
+    Explain(Box<Plan>),
+    Begin,
+    Commit,
+    Rollback,
+}
+
+/// How the executor should fetch candidate rows for a `SELECT`: either a full
+/// table scan, or a lookup/range scan against a sled-backed secondary index with
+/// the remaining predicate applied as a residual filter.
+#[derive(Debug, Clone)]
+pub enum AccessPath {
+    FullScan,
+    IndexScan {
+        index_column: String,
+        bounds: IndexBounds,
+        /// Always the full original WHERE expression, re-applied after the index
+        /// narrows the candidate set, so semantics match a full scan exactly even
+        /// when the index only covers part of the predicate.
+        residual: Expr,
+    },
+    /// The whole WHERE clause lowers to a `Predicate` tree whose every leaf is a
+    /// `CREATE INDEX`-backed column, so the executor can resolve it into a
+    /// `RoaringBitmap` of row-ids via `executor::bitmap` (intersecting `AND`s,
+    /// unioning `OR`s) instead of a byte-range scan of a single column.
+    BitmapScan { predicate: Predicate },
+}
+
+#[derive(Debug, Clone)]
+pub enum IndexBounds {
+    Eq(Value),
+    Range {
+        lower: Option<Value>,
+        upper: Option<Value>,
+    },
+}
+
+/// A `SELECT`'s logical query plan, built bottom-up by
+/// [`Planner::build_select_tree`]: a `Scan` at the leaf, optionally wrapped by
+/// a `Filter` the chosen `AccessPath` couldn't absorb, then a left-deep chain
+/// of `Join`s (one per `JOIN` clause), then `Aggregate`/`Sort`/`Limit` per the
+/// matching SQL clause, and always a `Project` at the root. Giving every
+/// stage a uniform `Box<PlanNode>`-linked shape is what lets a later
+/// rule-based rewrite (predicate pushdown, projection pruning) transform the
+/// tree instead of special-casing a flat summary.
+///
+/// This is the logical plan only: `Executor::execute` does not walk it to run
+/// a query. It still executes `SELECT`s directly off the `Statement` AST, via
+/// its own (already-working) access-path selection, and it still rejects any
+/// `SELECT` with a `JOIN` outright. `Planner::explain` does consume this tree
+/// to build `EXPLAIN`'s output, so it isn't just test-only scaffolding — but
+/// since a `Join` node describes something the executor can never actually
+/// run, `explain` rejects a joined `SELECT` up front rather than rendering a
+/// plan for a query that would fail if it were really executed.
+#[derive(Debug, Clone)]
+pub enum PlanNode {
+    Scan {
+        table: String,
+        /// The predicate absorbed into this scan's `access_path` (an
+        /// `IndexScan`'s or `BitmapScan`'s own filter); `None` when the scan
+        /// is a full scan, in which case an unresolved predicate shows up as
+        /// a wrapping `Filter` instead.
+        filter: Option<Predicate>,
+        access_path: AccessPath,
+    },
+    /// A predicate applied after `input` runs, because `input`'s access path
+    /// didn't absorb it (currently: a full scan under a `WHERE` clause).
+    Filter {
+        input: Box<PlanNode>,
+        predicate: Predicate,
+    },
+    /// One `JOIN` clause; `right` is always a `Scan` of the joined relation
+    /// today (NexumDB doesn't plan an access path for it, matching
+    /// `Executor::execute` not running joins yet).
+    Join {
+        left: Box<PlanNode>,
+        right: Box<PlanNode>,
+        on: Option<Expr>,
+        kind: JoinKind,
+    },
+    Aggregate {
+        input: Box<PlanNode>,
+        group_by: Vec<String>,
+        aggregates: Vec<SelectItem>,
+    },
+    Sort {
+        input: Box<PlanNode>,
+        keys: Vec<OrderByClause>,
+    },
+    Limit {
+        input: Box<PlanNode>,
+        count: usize,
+    },
+    /// The final projection; always the tree's root.
+    Project {
+        input: Box<PlanNode>,
+        columns: Vec<BoundColumn>,
     },
 }
 
+/// One annotated node of `Planner::explain`'s output: `id`/`parent_id` link
+/// nodes into a tree (root has `parent_id: None`) a caller can print indented
+/// by depth. Rows are listed input-first and `parent_id` points at a node's
+/// input, so the root is the table scan and the final `Project` is deepest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainRow {
+    pub id: usize,
+    pub parent_id: Option<usize>,
+    pub operation: String,
+    pub estimated_rows: usize,
+    pub estimated_cost: f64,
+}
+
 pub struct Planner;
 
 impl Planner {
-    pub fn plan(statement: Statement) -> Plan {
+    pub fn plan(catalog: &Catalog, statement: Statement) -> Result<Plan> {
         match statement {
-            Statement::CreateTable { name, columns } => {
+            Statement::CreateTable { name, columns, .. } => {
                 let cols = columns
                     .iter()
                     .map(|c| (c.name.clone(), format!("{:?}", c.data_type)))
                     .collect();
-                Plan::CreateTable {
+                Ok(Plan::CreateTable {
                     name,
                     columns: cols,
-                }
+                })
             }
-            Statement::Insert { table, values, .. } => Plan::Insert {
+            Statement::Insert { table, values, .. } => Ok(Plan::Insert {
                 table,
                 rows: values.len(),
-            },
-            Statement::Select { table, columns, .. } => Plan::Select { table, columns },
-            Statement::Delete { table, where_clause } => Plan::Delete {
+            }),
+            Statement::Select {
+                ref table,
+                ref joins,
+                ref projection,
+                ref where_clause,
+                ref group_by,
+                ref having,
+                ref order_by,
+                limit,
+            } => Ok(Plan::Select(Self::build_select_tree(
+                catalog,
+                table,
+                joins,
+                projection,
+                where_clause.as_deref(),
+                group_by,
+                having.as_deref(),
+                order_by.as_deref(),
+                limit,
+            )?)),
+            Statement::Update {
+                table,
+                assignments,
+                where_clause,
+            } => {
+                let schema = catalog.get_table(&table)?.ok_or_else(|| {
+                    StorageError::ReadError(format!("Table {} not found", table))
+                })?;
+
+                let assigns_primary_key = assignments.iter().try_fold(false, |acc, (name, _)| {
+                    schema
+                        .columns
+                        .iter()
+                        .find(|c| &c.name == name)
+                        .map(|c| acc || c.primary_key)
+                        .ok_or_else(|| {
+                            StorageError::WriteError(format!(
+                                "Column {} not found on table {}",
+                                name, table
+                            ))
+                        })
+                })?;
+
+                Ok(Plan::Update {
+                    table,
+                    assignments,
+                    assigns_primary_key,
+                    filter: where_clause.as_deref().and_then(Predicate::lower),
+                })
+            }
+            Statement::Delete { table, .. } => {
+                let fk_checks = catalog
+                    .tables_referencing(&table)?
+                    .into_iter()
+                    .map(|(child_table, fk)| FkCheck {
+                        child_table,
+                        child_column: fk.columns.join(", "),
+                        action: fk.on_delete,
+                    })
+                    .collect();
+                Ok(Plan::Delete { table, fk_checks })
+            }
+            Statement::ShowTables => Ok(Plan::ShowTables),
+            Statement::DescribeTable { name } => Ok(Plan::DescribeTable { name }),
+            Statement::DropTable { name, .. } => Ok(Plan::DropTable { name }),
+            Statement::AlterTable { name, operations } => Ok(Plan::AlterTable {
+                name,
+                operation_count: operations.len(),
+            }),
+            Statement::CreateTableAs { name, temporary, .. } => {
+                Ok(Plan::CreateTableAs { name, temporary })
+            }
+            Statement::CreateIndex { name, table, column } => {
+                Ok(Plan::CreateIndex { name, table, column })
+            }
+            Statement::Explain { query } => {
+                Ok(Plan::Explain(Box::new(Self::plan(catalog, *query)?)))
+            }
+            Statement::Begin => Ok(Plan::Begin),
+            Statement::Commit => Ok(Plan::Commit),
+            Statement::Rollback => Ok(Plan::Rollback),
+        }
+    }
+
+    /// Default fraction of a table's rows an index/bitmap scan is assumed to
+    /// return per predicate leaf, absent any real column statistics.
+    const DEFAULT_SELECTIVITY: f64 = 0.1;
+
+    /// Builds the recursive [`PlanNode`] tree for a `SELECT`: a `Scan` at the
+    /// leaf (optionally wrapped in a `Filter` when `access_path` is a full
+    /// scan under a `WHERE` clause), a left-deep chain of `Join`s for `joins`,
+    /// then `Aggregate`/`Sort`/`Limit` nodes per the matching clause, and a
+    /// `Project` at the root for `projection`.
+    fn build_select_tree(
+        catalog: &Catalog,
+        table: &str,
+        joins: &[JoinClause],
+        projection: &[SelectItem],
+        where_clause: Option<&Expr>,
+        group_by: &[String],
+        having: Option<&Expr>,
+        order_by: Option<&[OrderByClause]>,
+        limit: Option<usize>,
+    ) -> Result<PlanNode> {
+        let filter = where_clause.and_then(Predicate::lower);
+        let access_path = Self::choose_access_path(catalog, table, where_clause)?;
+        let scan_filter = match access_path {
+            AccessPath::FullScan => None,
+            _ => filter.clone(),
+        };
+
+        let mut root = PlanNode::Scan {
+            table: table.to_string(),
+            filter: scan_filter,
+            access_path: access_path.clone(),
+        };
+
+        if let (Some(predicate), AccessPath::FullScan) = (&filter, &access_path) {
+            root = PlanNode::Filter {
+                input: Box::new(root),
+                predicate: predicate.clone(),
+            };
+        }
+
+        for join in joins {
+            root = PlanNode::Join {
+                left: Box::new(root),
+                right: Box::new(PlanNode::Scan {
+                    table: join.relation.clone(),
+                    filter: None,
+                    access_path: AccessPath::FullScan,
+                }),
+                on: join.on.as_deref().cloned(),
+                kind: join.kind,
+            };
+        }
+
+        let has_aggregate_projection = projection
+            .iter()
+            .any(|item| matches!(item, SelectItem::Aggregate { .. }));
+        if !group_by.is_empty() || has_aggregate_projection || having.is_some() {
+            root = PlanNode::Aggregate {
+                input: Box::new(root),
+                group_by: group_by.to_vec(),
+                aggregates: projection
+                    .iter()
+                    .filter(|item| matches!(item, SelectItem::Aggregate { .. }))
+                    .cloned()
+                    .collect(),
+            };
+        }
+
+        if let Some(keys) = order_by {
+            root = PlanNode::Sort {
+                input: Box::new(root),
+                keys: keys.to_vec(),
+            };
+        }
+
+        if let Some(count) = limit {
+            root = PlanNode::Limit {
+                input: Box::new(root),
+                count,
+            };
+        }
+
+        let columns = Binder::bind_select_columns(catalog, table, projection)?;
+        Ok(PlanNode::Project {
+            input: Box::new(root),
+            columns,
+        })
+    }
+
+    /// The table name of a `Join`'s `right` child, which `build_select_tree`
+    /// always builds as a `Scan`.
+    fn join_relation_label(node: &PlanNode) -> &str {
+        match node {
+            PlanNode::Scan { table, .. } => table,
+            _ => "?",
+        }
+    }
+
+    /// Walks `node` depth-first, giving each operator its own `ExplainRow`
+    /// whose `parent_id` points at the row produced by its input (a `Join`'s
+    /// `right` side is used only to label the operation, not separately
+    /// costed, since NexumDB doesn't plan its own access path for it). Using
+    /// `catalog` for a real row count (`Catalog::row_count`) rather than a
+    /// guess: a full scan costs one row apiece, an equality lookup on the
+    /// primary key costs a constant 1, and any other index/bitmap scan is
+    /// discounted by `DEFAULT_SELECTIVITY`. Returns the id and row/cost
+    /// estimate of the row just pushed, for the caller wrapping this node.
+    fn flatten_plan_node(
+        catalog: &Catalog,
+        node: &PlanNode,
+        rows: &mut Vec<ExplainRow>,
+        parent_id: Option<usize>,
+    ) -> Result<(usize, usize, f64)> {
+        match node {
+            PlanNode::Scan {
+                table, access_path, ..
+            } => {
+                let row_count = catalog.row_count(table)?;
+                let (operation, estimated_rows, estimated_cost) = match access_path {
+                    AccessPath::FullScan => {
+                        (format!("SeqScan({})", table), row_count, row_count as f64)
+                    }
+                    AccessPath::IndexScan {
+                        index_column,
+                        bounds,
+                        ..
+                    } => {
+                        let is_pk_point_lookup = matches!(bounds, IndexBounds::Eq(_))
+                            && catalog
+                                .get_table(table)?
+                                .map(|schema| {
+                                    schema
+                                        .columns
+                                        .iter()
+                                        .any(|c| &c.name == index_column && c.primary_key)
+                                })
+                                .unwrap_or(false);
+
+                        let operation = format!("IndexScan({}.{})", table, index_column);
+                        if is_pk_point_lookup {
+                            (operation, 1, 1.0)
+                        } else {
+                            let cost = row_count as f64 * Self::DEFAULT_SELECTIVITY;
+                            (operation, cost.round() as usize, cost)
+                        }
+                    }
+                    AccessPath::BitmapScan { .. } => {
+                        let cost = row_count as f64 * Self::DEFAULT_SELECTIVITY;
+                        (format!("BitmapScan({})", table), cost.round() as usize, cost)
+                    }
+                };
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id,
+                    operation,
+                    estimated_rows,
+                    estimated_cost,
+                });
+                Ok((id, estimated_rows, estimated_cost))
+            }
+            PlanNode::Filter { input, predicate } => {
+                let (input_id, input_rows, _) =
+                    Self::flatten_plan_node(catalog, input, rows, parent_id)?;
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(input_id),
+                    operation: format!("Filter({:?})", predicate),
+                    estimated_rows: input_rows,
+                    estimated_cost: input_rows as f64,
+                });
+                Ok((id, input_rows, input_rows as f64))
+            }
+            PlanNode::Join { left, right, kind, .. } => {
+                let (left_id, left_rows, _) =
+                    Self::flatten_plan_node(catalog, left, rows, parent_id)?;
+                let relation = Self::join_relation_label(right);
+                let relation_rows = catalog.row_count(relation)?;
+                let join_rows = left_rows.saturating_mul(relation_rows.max(1));
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(left_id),
+                    operation: format!("{:?}Join({})", kind, relation),
+                    estimated_rows: join_rows,
+                    estimated_cost: join_rows as f64,
+                });
+                Ok((id, join_rows, join_rows as f64))
+            }
+            PlanNode::Aggregate {
+                input, group_by, ..
+            } => {
+                let (input_id, input_rows, _) =
+                    Self::flatten_plan_node(catalog, input, rows, parent_id)?;
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(input_id),
+                    operation: format!("Aggregate({})", group_by.join(", ")),
+                    estimated_rows: input_rows,
+                    estimated_cost: input_rows as f64,
+                });
+                Ok((id, input_rows, input_rows as f64))
+            }
+            PlanNode::Sort { input, keys } => {
+                let (input_id, input_rows, _) =
+                    Self::flatten_plan_node(catalog, input, rows, parent_id)?;
+                let keys_label = keys
+                    .iter()
+                    .map(|clause| {
+                        format!(
+                            "{} {}",
+                            clause.column,
+                            if clause.ascending { "ASC" } else { "DESC" }
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(input_id),
+                    operation: format!("Sort({})", keys_label),
+                    estimated_rows: input_rows,
+                    estimated_cost: input_rows as f64,
+                });
+                Ok((id, input_rows, input_rows as f64))
+            }
+            PlanNode::Limit { input, count } => {
+                let (input_id, input_rows, _) =
+                    Self::flatten_plan_node(catalog, input, rows, parent_id)?;
+                let estimated_rows = input_rows.min(*count);
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(input_id),
+                    operation: format!("Limit({})", count),
+                    estimated_rows,
+                    estimated_cost: estimated_rows as f64,
+                });
+                Ok((id, estimated_rows, estimated_rows as f64))
+            }
+            PlanNode::Project { input, columns } => {
+                let (input_id, input_rows, _) =
+                    Self::flatten_plan_node(catalog, input, rows, parent_id)?;
+                let label = if columns.is_empty() {
+                    "*".to_string()
+                } else {
+                    columns
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let id = rows.len();
+                rows.push(ExplainRow {
+                    id,
+                    parent_id: Some(input_id),
+                    operation: format!("Project({})", label),
+                    estimated_rows: input_rows,
+                    estimated_cost: input_rows as f64,
+                });
+                Ok((id, input_rows, input_rows as f64))
+            }
+        }
+    }
+
+    /// Plans `statement` and annotates it with a per-node cost estimate. A
+    /// `SELECT` is planned into a [`PlanNode`] tree via `build_select_tree`
+    /// and flattened node-by-node with `flatten_plan_node`; every other
+    /// statement gets a single flat row describing its `Plan`.
+    pub fn explain(catalog: &Catalog, statement: Statement) -> Result<Vec<ExplainRow>> {
+        if let Statement::Select {
+            table,
+            joins,
+            projection,
+            where_clause,
+            group_by,
+            having,
+            order_by,
+            limit,
+        } = &statement
+        {
+            // `Executor::execute` rejects any `SELECT` with a `JOIN` outright
+            // (it doesn't run the plan tree), so `EXPLAIN` must refuse the same
+            // query instead of describing a join it can never actually run.
+            if !joins.is_empty() {
+                return Err(StorageError::ReadError(
+                    "JOIN execution is not yet supported".to_string(),
+                ));
+            }
+
+            let tree = Self::build_select_tree(
+                catalog,
                 table,
-                has_where: where_clause.is_some(),
+                joins,
+                projection,
+                where_clause.as_deref(),
+                group_by,
+                having.as_deref(),
+                order_by.as_deref(),
+                *limit,
+            )?;
+
+            let mut rows = Vec::new();
+            Self::flatten_plan_node(catalog, &tree, &mut rows, None)?;
+            return Ok(rows);
+        }
+
+        let plan = Self::plan(catalog, statement)?;
+        Ok(vec![ExplainRow {
+            id: 0,
+            parent_id: None,
+            operation: format!("{:?}", plan),
+            estimated_rows: 0,
+            estimated_cost: 0.0,
+        }])
+    }
+
+    /// Decomposes the top-level WHERE conjunction into atomic predicates and picks
+    /// the first one whose column has a matching index, preferring an equality
+    /// predicate (a point lookup) over an inequality (a range scan). Tried only
+    /// after a whole-predicate bitmap scan isn't available.
+    pub fn choose_access_path(
+        catalog: &Catalog,
+        table: &str,
+        where_clause: Option<&Expr>,
+    ) -> Result<AccessPath> {
+        let Some(expr) = where_clause else {
+            return Ok(AccessPath::FullScan);
+        };
+
+        if let Some(predicate) = Predicate::lower(expr) {
+            if Self::bitmap_resolvable(catalog, table, &predicate)? {
+                return Ok(AccessPath::BitmapScan { predicate });
+            }
+        }
+
+        let mut atoms = Vec::new();
+        Self::decompose_conjunction(expr, &mut atoms);
+
+        let mut best: Option<(String, IndexBounds)> = None;
+        for atom in &atoms {
+            let Some((column, bounds)) = Self::sargable_predicate(atom) else {
+                continue;
+            };
+            if catalog.index_for_column(table, &column)?.is_none() {
+                continue;
+            }
+            let is_eq = matches!(bounds, IndexBounds::Eq(_));
+            match &best {
+                Some((_, IndexBounds::Eq(_))) => {}
+                _ => {
+                    if is_eq || best.is_none() {
+                        best = Some((column, bounds));
+                    }
+                }
+            }
+        }
+
+        match best {
+            Some((index_column, bounds)) => Ok(AccessPath::IndexScan {
+                index_column,
+                bounds,
+                residual: expr.clone(),
+            }),
+            None => Ok(AccessPath::FullScan),
+        }
+    }
+
+    /// True if every leaf of `predicate` is a `CREATE INDEX`-backed column
+    /// compared in a shape a bitmap posting list can serve (`=`, `IN`,
+    /// `BETWEEN`, combined with `AND`/`OR`). A single unsupported leaf (e.g. a
+    /// `LIKE`, an unindexed column, or a negated `IN`/`BETWEEN`) makes the whole
+    /// tree unresolvable, since there's no bitmap to combine it with.
+    fn bitmap_resolvable(catalog: &Catalog, table: &str, predicate: &Predicate) -> Result<bool> {
+        Ok(match predicate {
+            Predicate::Comparison {
+                column,
+                op: CompareOp::Eq,
+                ..
+            } => catalog.is_bitmap_indexed(table, column)?,
+            Predicate::In {
+                column,
+                negated: false,
+                ..
+            } => catalog.is_bitmap_indexed(table, column)?,
+            Predicate::Between {
+                column,
+                negated: false,
+                ..
+            } => catalog.is_bitmap_indexed(table, column)?,
+            Predicate::And(left, right) | Predicate::Or(left, right) => {
+                Self::bitmap_resolvable(catalog, table, left)?
+                    && Self::bitmap_resolvable(catalog, table, right)?
+            }
+            _ => false,
+        })
+    }
+
+    fn decompose_conjunction<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+        if let Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } = expr
+        {
+            Self::decompose_conjunction(left, out);
+            Self::decompose_conjunction(right, out);
+        } else {
+            out.push(expr);
+        }
+    }
+
+    /// Recognizes a single-column comparison or `BETWEEN` atom that a
+    /// byte-range index can serve, via `Predicate::lower` rather than matching
+    /// `BinaryOperator`/`Value` directly, returning the indexed column name and
+    /// the bounds an index scan would use.
+    fn sargable_predicate(expr: &Expr) -> Option<(String, IndexBounds)> {
+        match Predicate::lower(expr)? {
+            Predicate::Comparison { column, op, value } => {
+                let bounds = match op {
+                    CompareOp::Eq => IndexBounds::Eq(value),
+                    CompareOp::Gt | CompareOp::GtEq => IndexBounds::Range {
+                        lower: Some(value),
+                        upper: None,
+                    },
+                    CompareOp::Lt | CompareOp::LtEq => IndexBounds::Range {
+                        lower: None,
+                        upper: Some(value),
+                    },
+                    CompareOp::NotEq => return None,
+                };
+                Some((column, bounds))
+            }
+            Predicate::Between {
+                column,
+                low,
+                high,
+                negated: false,
+            } => Some((
+                column,
+                IndexBounds::Range {
+                    lower: Some(low),
+                    upper: Some(high),
+                },
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::types::{Column, DataType};
+    use crate::storage::StorageEngine;
+    use sqlparser::ast::{Expr, Ident, Value as SqlValue};
+
+    fn setup() -> Catalog {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+        catalog
+            .create_table(
+                "users",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("age", DataType::Integer),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+        catalog
+    }
+
+    fn eq_expr(column: &str, n: i64) -> Expr {
+        Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new(column))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Value(SqlValue::Number(n.to_string(), false))),
+        }
+    }
+
+    fn int_lit(n: i64) -> Expr {
+        Expr::Value(SqlValue::Number(n.to_string(), false))
+    }
+
+    fn text_lit(s: &str) -> Expr {
+        Expr::Value(SqlValue::SingleQuotedString(s.to_string()))
+    }
+
+    #[test]
+    fn test_no_where_is_full_scan() {
+        let catalog = setup();
+        let path = Planner::choose_access_path(&catalog, "users", None).unwrap();
+        assert!(matches!(path, AccessPath::FullScan));
+    }
+
+    #[test]
+    fn test_unindexed_column_is_full_scan() {
+        let catalog = setup();
+        let expr = eq_expr("id", 42);
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        assert!(matches!(path, AccessPath::FullScan));
+    }
+
+    #[test]
+    fn test_indexed_eq_predicate_chooses_index_scan() {
+        let catalog = setup();
+        catalog.create_index("users", "id").unwrap();
+        let expr = eq_expr("id", 42);
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        match path {
+            AccessPath::IndexScan {
+                index_column,
+                bounds,
+                residual,
+            } => {
+                assert_eq!(index_column, "id");
+                assert!(matches!(bounds, IndexBounds::Eq(Value::Integer(42))));
+                assert_eq!(residual, expr);
+            }
+            _ => panic!("Expected IndexScan"),
+        }
+    }
+
+    #[test]
+    fn test_partial_match_still_applies_full_residual() {
+        let catalog = setup();
+        catalog.create_index("users", "id").unwrap();
+
+        let expr = Expr::BinaryOp {
+            left: Box::new(eq_expr("id", 42)),
+            op: BinaryOperator::And,
+            right: Box::new(eq_expr("age", 30)),
+        };
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        match path {
+            AccessPath::IndexScan { residual, .. } => assert_eq!(residual, expr),
+            _ => panic!("Expected IndexScan"),
+        }
+    }
+
+    #[test]
+    fn test_bitmap_indexed_eq_predicate_chooses_bitmap_scan() {
+        let catalog = setup();
+        catalog.create_bitmap_index("users", "age").unwrap();
+        let expr = eq_expr("age", 30);
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        assert!(matches!(path, AccessPath::BitmapScan { .. }));
+    }
+
+    #[test]
+    fn test_bitmap_scan_preferred_over_byte_range_index_scan() {
+        let catalog = setup();
+        catalog.create_index("users", "id").unwrap();
+        catalog.create_bitmap_index("users", "age").unwrap();
+
+        let expr = Expr::BinaryOp {
+            left: Box::new(eq_expr("id", 42)),
+            op: BinaryOperator::And,
+            right: Box::new(eq_expr("age", 30)),
+        };
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        assert!(matches!(path, AccessPath::BitmapScan { .. }));
+    }
+
+    #[test]
+    fn test_one_unindexed_leaf_falls_back_to_byte_range_index_scan() {
+        let catalog = setup();
+        catalog.create_index("users", "id").unwrap();
+        catalog.create_bitmap_index("users", "age").unwrap();
+
+        // `name` has no index of either kind, so the conjunction as a whole
+        // can't resolve to a single bitmap; the byte-range scan on `id` still
+        // applies with the rest as residual.
+        let expr = Expr::BinaryOp {
+            left: Box::new(eq_expr("id", 42)),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::IsNull(Box::new(Expr::Identifier(Ident::new("name"))))),
+        };
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        match path {
+            AccessPath::IndexScan { index_column, .. } => assert_eq!(index_column, "id"),
+            _ => panic!("Expected IndexScan"),
+        }
+    }
+
+    #[test]
+    fn test_between_on_indexed_column_chooses_index_scan() {
+        let catalog = setup();
+        catalog.create_index("users", "age").unwrap();
+
+        let expr = Expr::Between {
+            expr: Box::new(Expr::Identifier(Ident::new("age"))),
+            negated: false,
+            low: Box::new(Expr::Value(SqlValue::Number("18".to_string(), false))),
+            high: Box::new(Expr::Value(SqlValue::Number("65".to_string(), false))),
+        };
+
+        let path = Planner::choose_access_path(&catalog, "users", Some(&expr)).unwrap();
+        match path {
+            AccessPath::IndexScan {
+                index_column,
+                bounds,
+                ..
+            } => {
+                assert_eq!(index_column, "age");
+                assert!(matches!(
+                    bounds,
+                    IndexBounds::Range {
+                        lower: Some(Value::Integer(18)),
+                        upper: Some(Value::Integer(65)),
+                    }
+                ));
+            }
+            _ => panic!("Expected IndexScan"),
+        }
+    }
+
+    #[test]
+    fn test_explain_full_scan_costs_the_whole_table() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage.clone());
+        catalog
+            .create_table(
+                "users",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("age", DataType::Integer),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+
+        for n in 0..4 {
+            storage
+                .set(
+                    format!("data:users:{:04}", n).as_bytes(),
+                    serde_json::to_vec(&crate::executor::Row {
+                        values: vec![Value::Integer(n), Value::Integer(n)],
+                    })
+                    .unwrap()
+                    .as_slice(),
+                )
+                .unwrap();
+        }
+
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        let rows = Planner::explain(&catalog, statement).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].parent_id, None);
+        assert_eq!(rows[0].estimated_rows, 4);
+        assert_eq!(rows[1].parent_id, Some(0));
+        assert!(rows[0].operation.contains("SeqScan"));
+        assert_eq!(rows[1].estimated_rows, 4);
+        assert_eq!(rows[1].estimated_cost, 4.0);
+    }
+
+    #[test]
+    fn test_explain_primary_key_lookup_costs_one() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage.clone());
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        catalog
+            .create_table("accounts", vec![id_col], vec![], vec![])
+            .unwrap();
+        catalog.create_index("accounts", "id").unwrap();
+
+        for n in 0..10 {
+            storage
+                .set(
+                    format!("data:accounts:{:04}", n).as_bytes(),
+                    serde_json::to_vec(&crate::executor::Row {
+                        values: vec![Value::Integer(n)],
+                    })
+                    .unwrap()
+                    .as_slice(),
+                )
+                .unwrap();
+        }
+
+        let statement = Statement::Select {
+            table: "accounts".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: Some(Box::new(eq_expr("id", 5))),
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        let rows = Planner::explain(&catalog, statement).unwrap();
+        assert_eq!(rows[1].estimated_rows, 1);
+        assert_eq!(rows[1].estimated_cost, 1.0);
+    }
+
+    #[test]
+    fn test_explain_chains_sort_and_limit_after_the_scan() {
+        let catalog = setup();
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: Some(vec![crate::sql::types::OrderByClause {
+                column: "age".to_string(),
+                ascending: true,
+            }]),
+            limit: Some(1),
+        };
+
+        let rows = Planner::explain(&catalog, statement).unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1].parent_id, Some(0));
+        assert!(rows[1].operation.contains("Sort(age ASC)"));
+        assert_eq!(rows[2].parent_id, Some(1));
+        assert!(rows[2].operation.contains("Limit(1)"));
+        assert_eq!(rows[2].estimated_rows, rows[1].estimated_rows.min(1));
+    }
+
+    #[test]
+    fn test_explain_rejects_join_the_executor_cannot_run() {
+        let catalog = setup();
+        catalog
+            .create_table(
+                "orders",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("user_id", DataType::Integer),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![crate::sql::types::JoinClause {
+                relation: "orders".to_string(),
+                kind: crate::sql::types::JoinKind::Inner,
+                on: None,
+            }],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        let err = Planner::explain(&catalog, statement).unwrap_err();
+        assert!(err.to_string().contains("JOIN execution is not yet supported"));
+    }
+
+    #[test]
+    fn test_plan_select_wildcard_expands_to_bound_columns() {
+        let catalog = setup();
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Select(PlanNode::Project { columns, .. }) => {
+                assert_eq!(columns.len(), 2);
+                assert_eq!(columns[0].name, "id");
+                assert_eq!(columns[1].name, "age");
+            }
+            _ => panic!("Expected Plan::Select(PlanNode::Project { .. })"),
+        }
+    }
+
+    #[test]
+    fn test_plan_select_unknown_column_is_an_error() {
+        let catalog = setup();
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Column {
+                name: "nickname".to_string(),
+                alias: None,
+            }],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        assert!(Planner::plan(&catalog, statement).is_err());
+    }
+
+    #[test]
+    fn test_plan_select_carries_the_chosen_access_path() {
+        let catalog = setup();
+        catalog.create_index("users", "id").unwrap();
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: Some(Box::new(eq_expr("id", 42))),
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Select(PlanNode::Project { input, .. }) => {
+                assert!(matches!(*input, PlanNode::Scan { access_path: AccessPath::IndexScan { .. }, .. }));
+            }
+            _ => panic!("Expected Plan::Select(PlanNode::Project { .. })"),
+        }
+    }
+
+    #[test]
+    fn test_plan_select_with_join_produces_a_join_node() {
+        let catalog = setup();
+        catalog
+            .create_table(
+                "orders",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("user_id", DataType::Integer),
+                ],
+                vec![],
+                vec![],
+            )
+            .unwrap();
+
+        let statement = Statement::Select {
+            table: "users".to_string(),
+            joins: vec![crate::sql::types::JoinClause {
+                relation: "orders".to_string(),
+                kind: crate::sql::types::JoinKind::Inner,
+                on: None,
+            }],
+            projection: vec![crate::sql::types::SelectItem::Wildcard],
+            where_clause: None,
+            group_by: vec![],
+            having: None,
+            order_by: None,
+            limit: None,
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Select(PlanNode::Project { input, .. }) => match *input {
+                PlanNode::Join { right, kind, .. } => {
+                    assert_eq!(kind, crate::sql::types::JoinKind::Inner);
+                    assert_eq!(Planner::join_relation_label(&right), "orders");
+                }
+                _ => panic!("Expected PlanNode::Join"),
             },
+            _ => panic!("Expected Plan::Select(PlanNode::Project { .. })"),
+        }
+    }
+
+    #[test]
+    fn test_plan_delete_lists_referencing_tables_as_fk_checks() {
+        use crate::sql::types::ForeignKey;
+
+        let catalog = setup();
+        catalog
+            .create_table(
+                "orders",
+                vec![
+                    Column::new("id", DataType::Integer),
+                    Column::new("user_id", DataType::Integer),
+                ],
+                vec![ForeignKey {
+                    columns: vec!["user_id".to_string()],
+                    ref_table: "users".to_string(),
+                    ref_columns: vec!["id".to_string()],
+                    on_delete: ReferentialAction::Cascade,
+                }],
+                vec![],
+            )
+            .unwrap();
+
+        let statement = Statement::Delete {
+            table: "users".to_string(),
+            where_clause: None,
+            returning: None,
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Delete { fk_checks, .. } => {
+                assert_eq!(fk_checks.len(), 1);
+                assert_eq!(fk_checks[0].child_table, "orders");
+                assert_eq!(fk_checks[0].child_column, "user_id");
+                assert_eq!(fk_checks[0].action, ReferentialAction::Cascade);
+            }
+            _ => panic!("Expected Plan::Delete"),
         }
     }
+
+    #[test]
+    fn test_plan_update_carries_assignments_and_filter() {
+        let catalog = setup();
+        let statement = Statement::Update {
+            table: "users".to_string(),
+            assignments: vec![("age".to_string(), int_lit(30))],
+            where_clause: Some(Box::new(eq_expr("id", 1))),
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Update {
+                assignments,
+                assigns_primary_key,
+                filter,
+                ..
+            } => {
+                assert_eq!(assignments, vec![("age".to_string(), int_lit(30))]);
+                assert!(!assigns_primary_key);
+                assert_eq!(
+                    filter,
+                    Some(Predicate::Comparison {
+                        column: "id".to_string(),
+                        op: CompareOp::Eq,
+                        value: Value::Integer(1),
+                    })
+                );
+            }
+            _ => panic!("Expected Plan::Update"),
+        }
+    }
+
+    #[test]
+    fn test_plan_update_flags_a_primary_key_assignment() {
+        let storage = StorageEngine::memory().unwrap();
+        let catalog = Catalog::new(storage);
+        let mut id_col = Column::new("id", DataType::Integer);
+        id_col.primary_key = true;
+        catalog
+            .create_table("accounts", vec![id_col], vec![], vec![])
+            .unwrap();
+
+        let statement = Statement::Update {
+            table: "accounts".to_string(),
+            assignments: vec![("id".to_string(), int_lit(2))],
+            where_clause: None,
+        };
+
+        match Planner::plan(&catalog, statement).unwrap() {
+            Plan::Update {
+                assigns_primary_key, ..
+            } => assert!(assigns_primary_key),
+            _ => panic!("Expected Plan::Update"),
+        }
+    }
+
+    #[test]
+    fn test_plan_update_rejects_an_unknown_column() {
+        let catalog = setup();
+        let statement = Statement::Update {
+            table: "users".to_string(),
+            assignments: vec![("nickname".to_string(), text_lit("bob"))],
+            where_clause: None,
+        };
+
+        assert!(Planner::plan(&catalog, statement).is_err());
+    }
 }
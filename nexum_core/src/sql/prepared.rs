@@ -0,0 +1,528 @@
+//! Prepare/bind/execute lifecycle for reusing a parsed `Statement` across calls
+//! with different literal values, so the same `SELECT ... WHERE id = $1` style
+//! query can be parsed once and bound to many argument sets.
+
+use super::parser::Parser;
+use super::types::{Statement, Value};
+use anyhow::{anyhow, Result};
+use sqlparser::ast::{self, Expr};
+use std::collections::HashMap;
+
+/// A parsed `Statement` plus how many distinct placeholders (`$1`/`?`) it
+/// contains. Cheap to clone so it can be handed out by `StatementCache` and bound
+/// repeatedly without re-parsing the source SQL.
+#[derive(Debug, Clone)]
+pub struct PreparedStatement {
+    pub statement: Statement,
+    pub param_count: usize,
+}
+
+impl PreparedStatement {
+    /// Parses `sql` and counts its placeholders.
+    pub fn prepare(sql: &str) -> Result<Self> {
+        let statement = Parser::parse(sql)?;
+        let param_count = Self::count_placeholders(&statement);
+        Ok(Self {
+            statement,
+            param_count,
+        })
+    }
+
+    /// Substitutes `args` for this statement's placeholders, returning a
+    /// ready-to-execute `Statement`. `args[i]` fills every `Placeholder(i)`.
+    pub fn bind(&self, args: &[Value]) -> Result<Statement> {
+        if args.len() != self.param_count {
+            return Err(anyhow!(
+                "Expected {} bound argument(s), got {}",
+                self.param_count,
+                args.len()
+            ));
+        }
+
+        Self::bind_statement(self.statement.clone(), args)
+    }
+
+    fn count_placeholders(statement: &Statement) -> usize {
+        let mut max_seen = 0usize;
+        let mut has_any = false;
+
+        let mut note = |value: &Value| {
+            if let Value::Placeholder(idx) = value {
+                has_any = true;
+                max_seen = max_seen.max(idx + 1);
+            }
+        };
+
+        if let Statement::Insert { values, .. } = statement {
+            for row in values {
+                for value in row {
+                    note(value);
+                }
+            }
+        }
+
+        // Placeholders inside a WHERE/HAVING clause, or an UPDATE assignment's
+        // RHS, live in the raw `sqlparser` expression tree rather than our
+        // `Value`, so they're counted separately. `Parser::convert_statement`
+        // already numbers every clause of a statement off one shared counter,
+        // so the max across whichever of them are present is this statement's
+        // highest placeholder.
+        let mut expr_count = 0usize;
+        for expr in Self::placeholder_clauses(statement) {
+            expr_count = expr_count.max(Self::count_expr_placeholders(expr));
+        }
+
+        if has_any || expr_count > 0 {
+            max_seen.max(expr_count)
+        } else {
+            0
+        }
+    }
+
+    fn count_expr_placeholders(expr: &Expr) -> usize {
+        let mut max_seen = 0usize;
+        Self::visit_expr(expr, &mut |token| {
+            if let Ok(idx) = Parser::placeholder_index(token, &mut 0) {
+                max_seen = max_seen.max(idx + 1);
+            }
+        });
+        max_seen
+    }
+
+    /// Every WHERE/HAVING expression `statement` carries, which is where a
+    /// raw-`Expr` placeholder (as opposed to a lowered `Value::Placeholder`)
+    /// can appear.
+    fn placeholder_clauses(statement: &Statement) -> Vec<&Expr> {
+        match statement {
+            Statement::Select {
+                where_clause,
+                having,
+                ..
+            } => where_clause
+                .as_deref()
+                .into_iter()
+                .chain(having.as_deref())
+                .collect(),
+            Statement::Update {
+                assignments,
+                where_clause,
+                ..
+            } => assignments
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(where_clause.as_deref())
+                .collect(),
+            Statement::Delete { where_clause, .. } => where_clause.as_deref().into_iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Walks `expr`'s commonly-used variants, invoking `on_token` with each
+    /// placeholder's raw token text (`"$1"`, `"?"`, ...). Variants this repo
+    /// doesn't otherwise evaluate (subqueries, nested function calls beyond a
+    /// single argument) are left alone rather than guessed at.
+    fn visit_expr(expr: &Expr, on_token: &mut impl FnMut(&str)) {
+        match expr {
+            Expr::Value(ast::Value::Placeholder(token)) => on_token(token),
+            Expr::BinaryOp { left, right, .. } => {
+                Self::visit_expr(left, on_token);
+                Self::visit_expr(right, on_token);
+            }
+            Expr::UnaryOp { expr, .. } => Self::visit_expr(expr, on_token),
+            Expr::Nested(inner) | Expr::IsNull(inner) | Expr::IsNotNull(inner) => {
+                Self::visit_expr(inner, on_token)
+            }
+            Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+                Self::visit_expr(expr, on_token);
+                Self::visit_expr(pattern, on_token);
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                Self::visit_expr(expr, on_token);
+                Self::visit_expr(low, on_token);
+                Self::visit_expr(high, on_token);
+            }
+            Expr::InList { expr, list, .. } => {
+                Self::visit_expr(expr, on_token);
+                for item in list {
+                    Self::visit_expr(item, on_token);
+                }
+            }
+            Expr::AnyOp { left, right, .. } | Expr::AllOp { left, right, .. } => {
+                Self::visit_expr(left, on_token);
+                Self::visit_expr(right, on_token);
+            }
+            _ => {}
+        }
+    }
+
+    /// Rebuilds `statement` with every `Value::Placeholder`/raw-expression
+    /// placeholder replaced by its bound argument.
+    fn bind_statement(statement: Statement, args: &[Value]) -> Result<Statement> {
+        match statement {
+            Statement::Insert {
+                table,
+                columns,
+                values,
+                returning,
+                on_conflict,
+            } => {
+                let values = values
+                    .into_iter()
+                    .map(|row| row.into_iter().map(|v| Self::bind_value(v, args)).collect())
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Statement::Insert {
+                    table,
+                    columns,
+                    values,
+                    returning,
+                    on_conflict,
+                })
+            }
+            Statement::Update {
+                table,
+                assignments,
+                where_clause,
+            } => {
+                // Assignments are numbered before the WHERE clause (matching
+                // `Parser::convert_statement`'s shared counter), so both walk
+                // the same `next_anon` sequence rather than each restarting
+                // from 0.
+                let mut next_anon = 0usize;
+                let assignments = assignments
+                    .into_iter()
+                    .map(|(col, expr)| Ok((col, Self::bind_expr(&expr, args, &mut next_anon)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                let where_clause = where_clause
+                    .map(|expr| Self::bind_expr(&expr, args, &mut next_anon))
+                    .transpose()?
+                    .map(Box::new);
+                Ok(Statement::Update {
+                    table,
+                    assignments,
+                    where_clause,
+                })
+            }
+            Statement::Delete {
+                table,
+                where_clause,
+                returning,
+            } => {
+                let where_clause = where_clause
+                    .map(|expr| Self::bind_expr(&expr, args, &mut 0))
+                    .transpose()?
+                    .map(Box::new);
+                Ok(Statement::Delete {
+                    table,
+                    where_clause,
+                    returning,
+                })
+            }
+            Statement::Select {
+                table,
+                joins,
+                projection,
+                where_clause,
+                group_by,
+                having,
+                order_by,
+                limit,
+            } => {
+                // `where_clause` and `having` share one counter, matching
+                // `Parser::convert_query` numbering both off the same
+                // sequence at parse time -- a bare `?` shouldn't be possible
+                // here (the parser already rewrote it to `$N`), but keeping
+                // a single counter across both avoids reintroducing the
+                // per-clause reset bug if that ever changes.
+                let mut next_anon = 0usize;
+                let where_clause = where_clause
+                    .map(|expr| Self::bind_expr(&expr, args, &mut next_anon))
+                    .transpose()?
+                    .map(Box::new);
+                let having = having
+                    .map(|expr| Self::bind_expr(&expr, args, &mut next_anon))
+                    .transpose()?
+                    .map(Box::new);
+                Ok(Statement::Select {
+                    table,
+                    joins,
+                    projection,
+                    where_clause,
+                    group_by,
+                    having,
+                    order_by,
+                    limit,
+                })
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn bind_value(value: Value, args: &[Value]) -> Result<Value> {
+        match value {
+            Value::Placeholder(idx) => args.get(idx).cloned().ok_or_else(|| {
+                anyhow!("No bound argument for placeholder ${}", idx + 1)
+            }),
+            other => Ok(other),
+        }
+    }
+
+    fn bind_expr(expr: &Expr, args: &[Value], next_anon: &mut usize) -> Result<Expr> {
+        if let Expr::Value(ast::Value::Placeholder(token)) = expr {
+            let idx = Parser::placeholder_index(token, next_anon)?;
+            let value = args
+                .get(idx)
+                .ok_or_else(|| anyhow!("No bound argument for placeholder ${}", idx + 1))?;
+            return Ok(Expr::Value(Self::value_to_sql_value(value)));
+        }
+
+        let bound = match expr {
+            Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                left: Box::new(Self::bind_expr(left, args, next_anon)?),
+                op: op.clone(),
+                right: Box::new(Self::bind_expr(right, args, next_anon)?),
+            },
+            Expr::UnaryOp { op, expr: inner } => Expr::UnaryOp {
+                op: op.clone(),
+                expr: Box::new(Self::bind_expr(inner, args, next_anon)?),
+            },
+            Expr::Nested(inner) => Expr::Nested(Box::new(Self::bind_expr(inner, args, next_anon)?)),
+            Expr::IsNull(inner) => Expr::IsNull(Box::new(Self::bind_expr(inner, args, next_anon)?)),
+            Expr::IsNotNull(inner) => {
+                Expr::IsNotNull(Box::new(Self::bind_expr(inner, args, next_anon)?))
+            }
+            Expr::Like {
+                negated,
+                expr: inner,
+                pattern,
+                escape_char,
+            } => Expr::Like {
+                negated: *negated,
+                expr: Box::new(Self::bind_expr(inner, args, next_anon)?),
+                pattern: Box::new(Self::bind_expr(pattern, args, next_anon)?),
+                escape_char: *escape_char,
+            },
+            Expr::ILike {
+                negated,
+                expr: inner,
+                pattern,
+                escape_char,
+            } => Expr::ILike {
+                negated: *negated,
+                expr: Box::new(Self::bind_expr(inner, args, next_anon)?),
+                pattern: Box::new(Self::bind_expr(pattern, args, next_anon)?),
+                escape_char: *escape_char,
+            },
+            Expr::Between {
+                expr: inner,
+                negated,
+                low,
+                high,
+            } => Expr::Between {
+                expr: Box::new(Self::bind_expr(inner, args, next_anon)?),
+                negated: *negated,
+                low: Box::new(Self::bind_expr(low, args, next_anon)?),
+                high: Box::new(Self::bind_expr(high, args, next_anon)?),
+            },
+            Expr::InList {
+                expr: inner,
+                list,
+                negated,
+            } => Expr::InList {
+                expr: Box::new(Self::bind_expr(inner, args, next_anon)?),
+                list: list
+                    .iter()
+                    .map(|item| Self::bind_expr(item, args, next_anon))
+                    .collect::<Result<Vec<_>>>()?,
+                negated: *negated,
+            },
+            Expr::AnyOp {
+                left,
+                compare_op,
+                right,
+            } => Expr::AnyOp {
+                left: Box::new(Self::bind_expr(left, args, next_anon)?),
+                compare_op: compare_op.clone(),
+                right: Box::new(Self::bind_expr(right, args, next_anon)?),
+            },
+            Expr::AllOp {
+                left,
+                compare_op,
+                right,
+            } => Expr::AllOp {
+                left: Box::new(Self::bind_expr(left, args, next_anon)?),
+                compare_op: compare_op.clone(),
+                right: Box::new(Self::bind_expr(right, args, next_anon)?),
+            },
+            other => other.clone(),
+        };
+
+        Ok(bound)
+    }
+
+    /// Renders a bound `Value` back into the `sqlparser` literal it was parsed
+    /// from, so it can be spliced into a WHERE/HAVING expression tree.
+    fn value_to_sql_value(value: &Value) -> ast::Value {
+        match value {
+            Value::Integer(n) => ast::Value::Number(n.to_string(), false),
+            Value::Float(f) => ast::Value::Number(f.to_string(), false),
+            Value::Text(s) => ast::Value::SingleQuotedString(s.clone()),
+            Value::Boolean(b) => ast::Value::Boolean(*b),
+            Value::Json(j) => ast::Value::SingleQuotedString(j.to_string()),
+            Value::Null => ast::Value::Null,
+            Value::Placeholder(_) => ast::Value::Null,
+        }
+    }
+}
+
+/// A registry of already-parsed statements keyed by their normalized SQL text,
+/// so repeated `PREPARE`/`EXECUTE` of the same query text skips re-parsing.
+#[derive(Debug, Default)]
+pub struct StatementCache {
+    statements: HashMap<String, PreparedStatement>,
+}
+
+impl StatementCache {
+    pub fn new() -> Self {
+        Self {
+            statements: HashMap::new(),
+        }
+    }
+
+    /// Returns the cached `PreparedStatement` for `sql`, parsing and caching it
+    /// first if this is the first time `sql` has been seen.
+    pub fn allocate(&mut self, sql: &str) -> Result<PreparedStatement> {
+        let key = Self::normalize(sql);
+        if let Some(existing) = self.statements.get(&key) {
+            return Ok(existing.clone());
+        }
+
+        let prepared = PreparedStatement::prepare(sql)?;
+        self.statements.insert(key, prepared.clone());
+        Ok(prepared)
+    }
+
+    /// Returns the cached `PreparedStatement` for `sql`, if `allocate` has
+    /// already been called for it.
+    pub fn lookup(&self, sql: &str) -> Option<&PreparedStatement> {
+        self.statements.get(&Self::normalize(sql))
+    }
+
+    /// Evicts `sql`'s cached plan, returning it if one was present.
+    pub fn deallocate(&mut self, sql: &str) -> Option<PreparedStatement> {
+        self.statements.remove(&Self::normalize(sql))
+    }
+
+    fn normalize(sql: &str) -> String {
+        sql.trim().trim_end_matches(';').trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare_counts_insert_placeholders() {
+        let prepared =
+            PreparedStatement::prepare("INSERT INTO users (id, name) VALUES ($1, $2)").unwrap();
+        assert_eq!(prepared.param_count, 2);
+    }
+
+    #[test]
+    fn test_bind_insert_substitutes_values() {
+        let prepared =
+            PreparedStatement::prepare("INSERT INTO users (id, name) VALUES ($1, $2)").unwrap();
+        let bound = prepared
+            .bind(&[Value::Integer(1), Value::Text("Alice".to_string())])
+            .unwrap();
+
+        match bound {
+            Statement::Insert { values, .. } => {
+                assert_eq!(
+                    values[0],
+                    vec![Value::Integer(1), Value::Text("Alice".to_string())]
+                );
+            }
+            _ => panic!("Expected Insert statement"),
+        }
+    }
+
+    #[test]
+    fn test_bind_wrong_argument_count_errors() {
+        let prepared =
+            PreparedStatement::prepare("INSERT INTO users (id) VALUES ($1)").unwrap();
+        assert!(prepared.bind(&[]).is_err());
+    }
+
+    #[test]
+    fn test_bind_where_clause_placeholder() {
+        let prepared = PreparedStatement::prepare("SELECT * FROM users WHERE id = $1").unwrap();
+        assert_eq!(prepared.param_count, 1);
+
+        let bound = prepared.bind(&[Value::Integer(42)]).unwrap();
+        match bound {
+            Statement::Select { where_clause, .. } => {
+                let expr = where_clause.unwrap();
+                assert!(!format!("{}", expr).contains('$'));
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
+    #[test]
+    fn test_update_numbers_set_and_where_placeholders_as_one_sequence() {
+        let prepared =
+            PreparedStatement::prepare("UPDATE users SET name = ? WHERE id = ?").unwrap();
+        assert_eq!(prepared.param_count, 2);
+
+        let bound = prepared
+            .bind(&[Value::Text("Bob".to_string()), Value::Integer(7)])
+            .unwrap();
+        match bound {
+            Statement::Update {
+                assignments,
+                where_clause,
+                ..
+            } => {
+                assert!(!format!("{}", assignments[0].1).contains('?'));
+                assert!(!format!("{}", where_clause.unwrap()).contains('?'));
+            }
+            _ => panic!("Expected Update statement"),
+        }
+    }
+
+    #[test]
+    fn test_having_only_placeholder_is_counted() {
+        let prepared = PreparedStatement::prepare(
+            "SELECT department, COUNT(*) FROM employees GROUP BY department HAVING COUNT(*) > ?",
+        )
+        .unwrap();
+        assert_eq!(prepared.param_count, 1);
+
+        let bound = prepared.bind(&[Value::Integer(5)]).unwrap();
+        match bound {
+            Statement::Select { having, .. } => {
+                assert!(!format!("{}", having.unwrap()).contains('?'));
+            }
+            _ => panic!("Expected Select statement"),
+        }
+    }
+
+    #[test]
+    fn test_statement_cache_reuses_parsed_plan() {
+        let mut cache = StatementCache::new();
+        assert!(cache.lookup("SELECT * FROM users").is_none());
+
+        let first = cache.allocate("SELECT * FROM users").unwrap();
+        let second = cache.allocate("SELECT * FROM users ").unwrap();
+        assert_eq!(first.param_count, second.param_count);
+        assert!(cache.lookup("select * from users").is_none());
+        assert!(cache.lookup("SELECT * FROM users").is_some());
+
+        let deallocated = cache.deallocate("SELECT * FROM users");
+        assert!(deallocated.is_some());
+        assert!(cache.lookup("SELECT * FROM users").is_none());
+    }
+}
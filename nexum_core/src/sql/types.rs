@@ -0,0 +1,587 @@
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{BinaryOperator, Expr, UnaryOperator, Value as SqlValue};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataType {
+    Integer,
+    Float,
+    Text,
+    Boolean,
+    Json,
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    Boolean(bool),
+    /// A nested document, navigable via a dotted/compound identifier path or the
+    /// `->`/`->>` operators (e.g. `properties.employees`, `properties->'employees'`).
+    Json(serde_json::Value),
+    Null,
+    /// An unbound parameter in a prepared statement (`$1`, `$2`, ... or a
+    /// positional `?`), numbered from 0 in first-appearance order. Resolved to a
+    /// concrete value by `PreparedStatement::bind` before execution; should never
+    /// reach the executor.
+    Placeholder(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Column {
+    pub name: String,
+    pub data_type: DataType,
+    #[serde(default)]
+    pub not_null: bool,
+    #[serde(default)]
+    pub primary_key: bool,
+    #[serde(default)]
+    pub unique: bool,
+    #[serde(default)]
+    pub default: Option<Value>,
+}
+
+impl Column {
+    /// A plain column with no constraints, as produced by a bare `name TYPE`
+    /// definition.
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            not_null: false,
+            primary_key: false,
+            unique: false,
+            default: None,
+        }
+    }
+}
+
+/// What to do to a referencing row when the row it points at is deleted, as
+/// declared by a `FOREIGN KEY ... ON DELETE` clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferentialAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+/// A table-level `FOREIGN KEY` constraint: `columns` in this table must match
+/// `ref_columns` in `ref_table`, unless a column's value is `NULL`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForeignKey {
+    pub columns: Vec<String>,
+    pub ref_table: String,
+    pub ref_columns: Vec<String>,
+    pub on_delete: ReferentialAction,
+}
+
+/// What `INSERT` should do when a row collides with an existing one on a
+/// declared `UNIQUE`/`PRIMARY KEY` column, as set by `INSERT OR REPLACE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OnConflict {
+    /// Fail with the usual uniqueness-violation error (the default).
+    Abort,
+    /// Overwrite the colliding row in place instead of failing.
+    Replace,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<Column>,
+    /// Column groups (from a `PRIMARY KEY`/`UNIQUE` column option or table
+    /// constraint) that must be unique as a combination; single-column groups
+    /// cover `Column::primary_key`/`Column::unique` too.
+    pub unique_groups: Vec<Vec<String>>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectItem {
+    Wildcard,
+    Column {
+        name: String,
+        alias: Option<String>,
+    },
+    /// A computed projection such as `price * quantity` or `UPPER(name)`.
+    Expr {
+        expr: Box<Expr>,
+        alias: Option<String>,
+    },
+    /// `COUNT`/`SUM`/`AVG`/`MIN`/`MAX` over a column, or over every row when
+    /// `column` is `None` (`COUNT(*)`).
+    Aggregate {
+        func: AggregateFunc,
+        column: Option<String>,
+        alias: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderByClause {
+    pub column: String,
+    pub ascending: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+/// One `JOIN`ed relation in a `SELECT`'s `FROM` clause: the table it joins in,
+/// how (`JoinKind`), and on what predicate. `on` is `None` only for `CROSS JOIN`
+/// or a join whose constraint NexumDB doesn't model yet (`NATURAL`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct JoinClause {
+    pub relation: String,
+    pub kind: JoinKind,
+    pub on: Option<Box<Expr>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Statement {
+    CreateTable {
+        name: String,
+        columns: Vec<Column>,
+        /// Composite (multi-column) `UNIQUE`/`PRIMARY KEY` table constraints;
+        /// single-column ones are folded into the matching `Column` instead.
+        unique_groups: Vec<Vec<String>>,
+        foreign_keys: Vec<ForeignKey>,
+    },
+    Insert {
+        table: String,
+        columns: Vec<String>,
+        values: Vec<Vec<Value>>,
+        /// `RETURNING col, ...`: when present, `Executor::execute` reports the
+        /// inserted rows (projected to these columns) instead of just a count.
+        returning: Option<Vec<String>>,
+        /// `INSERT OR REPLACE`: how to handle a row colliding with an existing
+        /// one on a declared `UNIQUE`/`PRIMARY KEY` column.
+        on_conflict: OnConflict,
+    },
+    Update {
+        table: String,
+        /// The RHS of each `SET col = ...`, kept as a raw expression (rather
+        /// than lowered to a literal `Value`) so it can reference the current
+        /// row, e.g. `SET price = price * 2`; evaluated per candidate row by
+        /// the executor's `ExpressionEvaluator`.
+        assignments: Vec<(String, Expr)>,
+        where_clause: Option<Box<Expr>>,
+    },
+    Delete {
+        table: String,
+        where_clause: Option<Box<Expr>>,
+        /// `RETURNING col, ...`: when present, `Executor::execute` reports the
+        /// deleted rows (projected to these columns) instead of just a count.
+        returning: Option<Vec<String>>,
+    },
+    Select {
+        table: String,
+        joins: Vec<JoinClause>,
+        projection: Vec<SelectItem>,
+        where_clause: Option<Box<Expr>>,
+        group_by: Vec<String>,
+        having: Option<Box<Expr>>,
+        order_by: Option<Vec<OrderByClause>>,
+        limit: Option<usize>,
+    },
+    ShowTables,
+    DescribeTable {
+        name: String,
+    },
+    DropTable {
+        name: String,
+        if_exists: bool,
+    },
+    AlterTable {
+        name: String,
+        operations: Vec<AlterTableOperation>,
+    },
+    /// `CREATE [TEMPORARY] TABLE name AS query`: materializes `query`'s result set
+    /// as a new table rather than declaring columns up front.
+    CreateTableAs {
+        name: String,
+        query: Box<Statement>,
+        /// A temporary table lives under a separate catalog prefix so it can be
+        /// dropped wholesale at session end instead of persisting like a base table.
+        temporary: bool,
+    },
+    /// `CREATE INDEX name ON table(column)`: backs `column` with a Roaring-bitmap
+    /// posting list (see `executor::bitmap`) the planner can resolve equality,
+    /// `IN`, and `BETWEEN` predicates against instead of a full table scan.
+    CreateIndex {
+        name: String,
+        table: String,
+        column: String,
+    },
+    /// `EXPLAIN query`: runs `query` through the `Planner` without executing it,
+    /// so the caller can see which `AccessPath` (full scan, byte-range index
+    /// scan, or bitmap scan) it would use.
+    Explain { query: Box<Statement> },
+    /// `BEGIN [TRANSACTION]`: starts buffering writes (see
+    /// `StorageEngine::begin`) so the statements that follow, until `COMMIT` or
+    /// `ROLLBACK`, apply atomically.
+    Begin,
+    /// `COMMIT`: applies every write staged since `Begin`.
+    Commit,
+    /// `ROLLBACK`: discards every write staged since `Begin`.
+    Rollback,
+}
+
+/// One clause of an `ALTER TABLE ... (op1, op2, ...)` statement. `sqlparser`
+/// allows several operations per statement; NexumDB applies them to the catalog
+/// and table data in order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlterTableOperation {
+    AddColumn { column: Column },
+    DropColumn { name: String },
+    RenameColumn { old_name: String, new_name: String },
+    RenameTable { new_name: String },
+}
+
+/// A comparison operator in a lowered [`Predicate`], kept separate from
+/// `sqlparser`'s `BinaryOperator` so callers match on just the handful of
+/// variants a predicate can use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl CompareOp {
+    /// The operator that keeps the same meaning with its operands swapped, used
+    /// when a literal appears on the left of a comparison (`1 < id` becomes
+    /// `id > 1`).
+    fn flip(self) -> Self {
+        match self {
+            CompareOp::Eq => CompareOp::Eq,
+            CompareOp::NotEq => CompareOp::NotEq,
+            CompareOp::Lt => CompareOp::Gt,
+            CompareOp::LtEq => CompareOp::GtEq,
+            CompareOp::Gt => CompareOp::Lt,
+            CompareOp::GtEq => CompareOp::LtEq,
+        }
+    }
+}
+
+/// An owned, `sqlparser`-independent predicate tree. `Predicate::lower` folds
+/// the common shapes of a `WHERE`/`HAVING` `Expr` into this once, so a module
+/// like the planner can match on field references and comparison operators
+/// directly instead of re-walking generic AST. Lowering only covers predicates
+/// over a single bare column (rejecting column-vs-column comparisons and
+/// anything it doesn't recognize, e.g. subqueries, JSON paths, or computed
+/// expressions); those keep going through the full `Expr` via
+/// `executor::filter::ExpressionEvaluator`, which remains the source of truth
+/// for row filtering.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Comparison {
+        column: String,
+        op: CompareOp,
+        value: Value,
+    },
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+    In {
+        column: String,
+        values: Vec<Value>,
+        negated: bool,
+    },
+    Between {
+        column: String,
+        low: Value,
+        high: Value,
+        negated: bool,
+    },
+    Like {
+        column: String,
+        pattern: String,
+        negated: bool,
+        case_insensitive: bool,
+    },
+}
+
+impl Predicate {
+    /// Lowers `expr` into a `Predicate`, or `None` if it (or any sub-expression)
+    /// falls outside the shapes listed on the type's doc comment.
+    pub fn lower(expr: &Expr) -> Option<Predicate> {
+        match expr {
+            Expr::BinaryOp { left, op, right } => match op {
+                BinaryOperator::And => Some(Predicate::And(
+                    Box::new(Self::lower(left)?),
+                    Box::new(Self::lower(right)?),
+                )),
+                BinaryOperator::Or => Some(Predicate::Or(
+                    Box::new(Self::lower(left)?),
+                    Box::new(Self::lower(right)?),
+                )),
+                _ => Self::lower_comparison(left, op, right),
+            },
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr,
+            } => Some(Predicate::Not(Box::new(Self::lower(expr)?))),
+            Expr::IsNull(inner) => Some(Predicate::IsNull {
+                column: Self::identifier_name(inner)?,
+                negated: false,
+            }),
+            Expr::IsNotNull(inner) => Some(Predicate::IsNull {
+                column: Self::identifier_name(inner)?,
+                negated: true,
+            }),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let column = Self::identifier_name(expr)?;
+                let values = list
+                    .iter()
+                    .map(Self::literal_value)
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Predicate::In {
+                    column,
+                    values,
+                    negated: *negated,
+                })
+            }
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => Some(Predicate::Between {
+                column: Self::identifier_name(expr)?,
+                low: Self::literal_value(low)?,
+                high: Self::literal_value(high)?,
+                negated: *negated,
+            }),
+            Expr::Like {
+                negated,
+                expr,
+                pattern,
+                escape_char: None,
+            } => Some(Predicate::Like {
+                column: Self::identifier_name(expr)?,
+                pattern: Self::literal_string(pattern)?,
+                negated: *negated,
+                case_insensitive: false,
+            }),
+            Expr::ILike {
+                negated,
+                expr,
+                pattern,
+                escape_char: None,
+            } => Some(Predicate::Like {
+                column: Self::identifier_name(expr)?,
+                pattern: Self::literal_string(pattern)?,
+                negated: *negated,
+                case_insensitive: true,
+            }),
+            _ => None,
+        }
+    }
+
+    fn lower_comparison(left: &Expr, op: &BinaryOperator, right: &Expr) -> Option<Predicate> {
+        let op = Self::lower_compare_op(op)?;
+
+        let (column, op, value) = match (left, right) {
+            (Expr::Identifier(ident), Expr::Value(v)) => {
+                (ident.value.clone(), op, Self::literal_value(v)?)
+            }
+            (Expr::Value(v), Expr::Identifier(ident)) => {
+                (ident.value.clone(), op.flip(), Self::literal_value(v)?)
+            }
+            // Column-vs-column comparisons aren't supported yet.
+            _ => return None,
+        };
+
+        Some(Predicate::Comparison { column, op, value })
+    }
+
+    fn lower_compare_op(op: &BinaryOperator) -> Option<CompareOp> {
+        match op {
+            BinaryOperator::Eq => Some(CompareOp::Eq),
+            BinaryOperator::NotEq => Some(CompareOp::NotEq),
+            BinaryOperator::Lt => Some(CompareOp::Lt),
+            BinaryOperator::LtEq => Some(CompareOp::LtEq),
+            BinaryOperator::Gt => Some(CompareOp::Gt),
+            BinaryOperator::GtEq => Some(CompareOp::GtEq),
+            _ => None,
+        }
+    }
+
+    fn identifier_name(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Identifier(ident) => Some(ident.value.clone()),
+            _ => None,
+        }
+    }
+
+    fn literal_value(expr: &Expr) -> Option<Value> {
+        match expr {
+            Expr::Value(v) => Self::literal_value_from_sql(v),
+            _ => None,
+        }
+    }
+
+    fn literal_value_from_sql(value: &SqlValue) -> Option<Value> {
+        match value {
+            SqlValue::Number(n, _) => {
+                if n.contains('.') {
+                    n.parse().ok().map(Value::Float)
+                } else {
+                    n.parse().ok().map(Value::Integer)
+                }
+            }
+            SqlValue::SingleQuotedString(s) | SqlValue::DoubleQuotedString(s) => {
+                Some(Value::Text(s.clone()))
+            }
+            SqlValue::Boolean(b) => Some(Value::Boolean(*b)),
+            SqlValue::Null => Some(Value::Null),
+            _ => None,
+        }
+    }
+
+    fn literal_string(expr: &Expr) -> Option<String> {
+        match Self::literal_value(expr)? {
+            Value::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlparser::ast::Ident;
+
+    fn identifier(name: &str) -> Expr {
+        Expr::Identifier(Ident::new(name))
+    }
+
+    fn number(n: i64) -> Expr {
+        Expr::Value(SqlValue::Number(n.to_string(), false))
+    }
+
+    #[test]
+    fn test_lower_comparison_flips_value_on_left() {
+        let expr = Expr::BinaryOp {
+            left: Box::new(number(18)),
+            op: BinaryOperator::Lt,
+            right: Box::new(identifier("age")),
+        };
+
+        assert_eq!(
+            Predicate::lower(&expr),
+            Some(Predicate::Comparison {
+                column: "age".to_string(),
+                op: CompareOp::Gt,
+                value: Value::Integer(18),
+            })
+        );
+    }
+
+    #[test]
+    fn test_lower_conjunction() {
+        let expr = Expr::BinaryOp {
+            left: Box::new(Expr::BinaryOp {
+                left: Box::new(identifier("id")),
+                op: BinaryOperator::Eq,
+                right: Box::new(number(1)),
+            }),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::IsNotNull(Box::new(identifier("name")))),
+        };
+
+        assert_eq!(
+            Predicate::lower(&expr),
+            Some(Predicate::And(
+                Box::new(Predicate::Comparison {
+                    column: "id".to_string(),
+                    op: CompareOp::Eq,
+                    value: Value::Integer(1),
+                }),
+                Box::new(Predicate::IsNull {
+                    column: "name".to_string(),
+                    negated: true,
+                }),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_lower_rejects_column_vs_column_comparison() {
+        let expr = Expr::BinaryOp {
+            left: Box::new(identifier("a")),
+            op: BinaryOperator::Eq,
+            right: Box::new(identifier("b")),
+        };
+
+        assert_eq!(Predicate::lower(&expr), None);
+    }
+
+    #[test]
+    fn test_lower_between_and_in() {
+        let between = Expr::Between {
+            expr: Box::new(identifier("price")),
+            negated: false,
+            low: Box::new(number(10)),
+            high: Box::new(number(20)),
+        };
+        assert_eq!(
+            Predicate::lower(&between),
+            Some(Predicate::Between {
+                column: "price".to_string(),
+                low: Value::Integer(10),
+                high: Value::Integer(20),
+                negated: false,
+            })
+        );
+
+        let in_list = Expr::InList {
+            expr: Box::new(identifier("status")),
+            list: vec![
+                Expr::Value(SqlValue::SingleQuotedString("active".to_string())),
+                Expr::Value(SqlValue::SingleQuotedString("pending".to_string())),
+            ],
+            negated: false,
+        };
+        assert_eq!(
+            Predicate::lower(&in_list),
+            Some(Predicate::In {
+                column: "status".to_string(),
+                values: vec![
+                    Value::Text("active".to_string()),
+                    Value::Text("pending".to_string())
+                ],
+                negated: false,
+            })
+        );
+    }
+}
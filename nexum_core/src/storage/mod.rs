@@ -0,0 +1,7 @@
+pub mod engine;
+pub mod error;
+
+pub use engine::{Backup, Progress, StorageEngine};
+pub use error::StorageError;
+
+pub type Result<T> = std::result::Result<T, StorageError>;
@@ -1,3 +1,4 @@
+use nexum_core::sql::{PreparedStatement, Value};
 use nexum_core::{Executor, Parser, StorageEngine};
 
 #[test]
@@ -54,6 +55,60 @@ fn test_in_operator_integration() {
     }
 }
 
+#[test]
+fn test_create_table_constraints_enforced_on_insert() {
+    let storage = StorageEngine::memory().unwrap();
+    let executor = Executor::new(storage);
+
+    let create = Parser::parse(
+        "CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, plan TEXT DEFAULT 'free')",
+    )
+    .unwrap();
+    executor.execute(create).unwrap();
+
+    let orders = Parser::parse(
+        "CREATE TABLE orders (id INTEGER, customer_id INTEGER, FOREIGN KEY (customer_id) REFERENCES customers(id))",
+    )
+    .unwrap();
+    executor.execute(orders).unwrap();
+
+    let insert = Parser::parse("INSERT INTO customers (id, email) VALUES (1, 'a@example.com')").unwrap();
+    executor.execute(insert).unwrap();
+
+    // Duplicate primary key is rejected.
+    let dup_pk = Parser::parse("INSERT INTO customers (id, email) VALUES (1, 'b@example.com')").unwrap();
+    assert!(executor.execute(dup_pk).is_err());
+
+    // Duplicate unique column is rejected.
+    let dup_unique =
+        Parser::parse("INSERT INTO customers (id, email) VALUES (2, 'a@example.com')").unwrap();
+    assert!(executor.execute(dup_unique).is_err());
+
+    // Missing NOT NULL column is rejected.
+    let missing_not_null = Parser::parse("INSERT INTO customers (id) VALUES (3)").unwrap();
+    assert!(executor.execute(missing_not_null).is_err());
+
+    // DEFAULT fills an omitted column.
+    let default_fill =
+        Parser::parse("INSERT INTO customers (id, email) VALUES (4, 'c@example.com')").unwrap();
+    executor.execute(default_fill).unwrap();
+    let select = Parser::parse("SELECT * FROM customers WHERE id = 4").unwrap();
+    match executor.execute(select).unwrap() {
+        nexum_core::executor::ExecutionResult::Selected { rows, .. } => {
+            assert_eq!(rows[0].values[2], nexum_core::sql::Value::Text("free".to_string()));
+        }
+        _ => panic!("Expected Selected result"),
+    }
+
+    // Foreign key with no matching customer is rejected.
+    let orphan_order = Parser::parse("INSERT INTO orders (id, customer_id) VALUES (1, 99)").unwrap();
+    assert!(executor.execute(orphan_order).is_err());
+
+    // Foreign key matching an existing customer succeeds.
+    let valid_order = Parser::parse("INSERT INTO orders (id, customer_id) VALUES (1, 1)").unwrap();
+    executor.execute(valid_order).unwrap();
+}
+
 #[test]
 fn test_between_with_order_limit() {
     let storage = StorageEngine::memory().unwrap();
@@ -81,3 +136,142 @@ fn test_between_with_order_limit() {
         _ => panic!("Expected Selected result"),
     }
 }
+
+#[test]
+fn test_prepared_statement_reused_with_different_arguments() {
+    let storage = StorageEngine::memory().unwrap();
+    let executor = Executor::new(storage);
+
+    let create = Parser::parse("CREATE TABLE accounts (id INTEGER, balance INTEGER)").unwrap();
+    executor.execute(create).unwrap();
+
+    let insert = PreparedStatement::prepare("INSERT INTO accounts (id, balance) VALUES ($1, $2)")
+        .unwrap();
+    executor
+        .execute_prepared(&insert, vec![Value::Integer(1), Value::Integer(100)])
+        .unwrap();
+    executor
+        .execute_prepared(&insert, vec![Value::Integer(2), Value::Integer(250)])
+        .unwrap();
+
+    let select = PreparedStatement::prepare("SELECT * FROM accounts WHERE id = $1").unwrap();
+    let result = executor
+        .execute_prepared(&select, vec![Value::Integer(2)])
+        .unwrap();
+
+    match result {
+        nexum_core::executor::ExecutionResult::Selected { rows, .. } => {
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].values[1], Value::Integer(250));
+        }
+        _ => panic!("Expected Selected result"),
+    }
+}
+
+#[test]
+fn test_alter_table_add_and_rename_column() {
+    let storage = StorageEngine::memory().unwrap();
+    let executor = Executor::new(storage);
+
+    let create = Parser::parse("CREATE TABLE contacts (id INTEGER, name TEXT)").unwrap();
+    executor.execute(create).unwrap();
+
+    let insert = Parser::parse("INSERT INTO contacts (id, name) VALUES (1, 'Ada')").unwrap();
+    executor.execute(insert).unwrap();
+
+    let add_column = Parser::parse("ALTER TABLE contacts ADD COLUMN email TEXT").unwrap();
+    executor.execute(add_column).unwrap();
+
+    let rename = Parser::parse("ALTER TABLE contacts RENAME COLUMN name TO full_name").unwrap();
+    executor.execute(rename).unwrap();
+
+    let select = Parser::parse("SELECT * FROM contacts").unwrap();
+    let result = executor.execute(select).unwrap();
+
+    match result {
+        nexum_core::executor::ExecutionResult::Selected { rows, .. } => {
+            assert_eq!(rows.len(), 1);
+            assert_eq!(
+                rows[0].values,
+                vec![Value::Integer(1), Value::Text("Ada".to_string()), Value::Null]
+            );
+        }
+        _ => panic!("Expected Selected result"),
+    }
+
+    let describe = Parser::parse("DESCRIBE contacts").unwrap();
+    match executor.execute(describe).unwrap() {
+        nexum_core::executor::ExecutionResult::Described { columns, .. } => {
+            assert_eq!(columns[1].name, "full_name");
+            assert_eq!(columns[2].name, "email");
+        }
+        _ => panic!("Expected Described result"),
+    }
+}
+
+#[test]
+fn test_create_table_as_select_materializes_query_result() {
+    let storage = StorageEngine::memory().unwrap();
+    let executor = Executor::new(storage);
+
+    let create = Parser::parse("CREATE TABLE users (id INTEGER, name TEXT, active BOOLEAN)").unwrap();
+    executor.execute(create).unwrap();
+
+    let insert = Parser::parse(
+        "INSERT INTO users VALUES (1, 'Ada', true), (2, 'Bob', false), (3, 'Cleo', true)",
+    )
+    .unwrap();
+    executor.execute(insert).unwrap();
+
+    let ctas = Parser::parse("CREATE TABLE active_users AS SELECT id, name FROM users WHERE active = true")
+        .unwrap();
+    executor.execute(ctas).unwrap();
+
+    let describe = Parser::parse("DESCRIBE active_users").unwrap();
+    match executor.execute(describe).unwrap() {
+        nexum_core::executor::ExecutionResult::Described { columns, .. } => {
+            assert_eq!(columns.len(), 2);
+            assert_eq!(columns[0].name, "id");
+            assert_eq!(columns[1].name, "name");
+        }
+        _ => panic!("Expected Described result"),
+    }
+
+    let select = Parser::parse("SELECT * FROM active_users ORDER BY id ASC").unwrap();
+    match executor.execute(select).unwrap() {
+        nexum_core::executor::ExecutionResult::Selected { rows, .. } => {
+            assert_eq!(rows.len(), 2);
+            assert_eq!(
+                rows[0].values,
+                vec![Value::Integer(1), Value::Text("Ada".to_string())]
+            );
+            assert_eq!(
+                rows[1].values,
+                vec![Value::Integer(3), Value::Text("Cleo".to_string())]
+            );
+        }
+        _ => panic!("Expected Selected result"),
+    }
+
+    // SHOW TABLES should list the base tables but not a temporary one.
+    let temp_ctas =
+        Parser::parse("CREATE TEMPORARY TABLE recent AS SELECT * FROM users").unwrap();
+    executor.execute(temp_ctas).unwrap();
+
+    match executor.execute(Parser::parse("SHOW TABLES").unwrap()).unwrap() {
+        nexum_core::executor::ExecutionResult::Tables { tables } => {
+            assert!(tables.contains(&"users".to_string()));
+            assert!(tables.contains(&"active_users".to_string()));
+            assert!(!tables.contains(&"recent".to_string()));
+        }
+        _ => panic!("Expected Tables result"),
+    }
+
+    let select_temp = Parser::parse("SELECT * FROM recent").unwrap();
+    match executor.execute(select_temp).unwrap() {
+        nexum_core::executor::ExecutionResult::Selected { rows, .. } => {
+            assert_eq!(rows.len(), 3);
+        }
+        _ => panic!("Expected Selected result"),
+    }
+}